@@ -0,0 +1,453 @@
+use serde::Serialize;
+
+/// the kind of a module specifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecifierKind {
+  /// a node builtin module, like `fs` or `node:fs`
+  Builtin,
+  /// `./lib` or `../lib`
+  Relative,
+  /// `/lib` or `file:///lib`
+  Absolute,
+  /// a package in `node_modules`
+  Bare,
+}
+
+/// a classified module specifier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Specifier {
+  pub kind: SpecifierKind,
+  /// the known export names of a builtin module
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub exports: Option<Vec<String>>,
+}
+
+/// a node version, like `18.17`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NodeVersion(pub u32, pub u32);
+
+impl NodeVersion {
+  /// parse a version like `v18.17.0`, `18.17` or `18`.
+  pub fn parse(version: &str) -> Option<Self> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = match parts.next() {
+      Some(minor) => minor.parse().ok()?,
+      None => 0,
+    };
+    Some(NodeVersion(major, minor))
+  }
+
+  // `require('node:fs')` is supported since 14.18 and 16.0
+  fn supports_node_prefix(&self) -> bool {
+    *self >= NodeVersion(16, 0) || (self.0 == 14 && *self >= NodeVersion(14, 18))
+  }
+}
+
+// the builtin modules and the version they were added in
+const BUILTINS: &[(&str, NodeVersion)] = &[
+  ("assert", NodeVersion(0, 0)),
+  ("assert/strict", NodeVersion(15, 0)),
+  ("async_hooks", NodeVersion(8, 1)),
+  ("buffer", NodeVersion(0, 0)),
+  ("child_process", NodeVersion(0, 0)),
+  ("cluster", NodeVersion(0, 0)),
+  ("console", NodeVersion(0, 0)),
+  ("constants", NodeVersion(0, 0)),
+  ("crypto", NodeVersion(0, 0)),
+  ("dgram", NodeVersion(0, 0)),
+  ("diagnostics_channel", NodeVersion(15, 1)),
+  ("dns", NodeVersion(0, 0)),
+  ("dns/promises", NodeVersion(15, 0)),
+  ("domain", NodeVersion(0, 0)),
+  ("events", NodeVersion(0, 0)),
+  ("fs", NodeVersion(0, 0)),
+  ("fs/promises", NodeVersion(14, 0)),
+  ("http", NodeVersion(0, 0)),
+  ("http2", NodeVersion(8, 4)),
+  ("https", NodeVersion(0, 0)),
+  ("inspector", NodeVersion(8, 0)),
+  ("module", NodeVersion(0, 0)),
+  ("net", NodeVersion(0, 0)),
+  ("os", NodeVersion(0, 0)),
+  ("path", NodeVersion(0, 0)),
+  ("path/posix", NodeVersion(15, 3)),
+  ("path/win32", NodeVersion(15, 3)),
+  ("perf_hooks", NodeVersion(8, 5)),
+  ("process", NodeVersion(0, 0)),
+  ("punycode", NodeVersion(0, 0)),
+  ("querystring", NodeVersion(0, 0)),
+  ("readline", NodeVersion(0, 0)),
+  ("readline/promises", NodeVersion(17, 0)),
+  ("repl", NodeVersion(0, 0)),
+  ("stream", NodeVersion(0, 0)),
+  ("stream/consumers", NodeVersion(16, 7)),
+  ("stream/promises", NodeVersion(15, 0)),
+  ("stream/web", NodeVersion(16, 5)),
+  ("string_decoder", NodeVersion(0, 0)),
+  ("sys", NodeVersion(0, 0)),
+  ("timers", NodeVersion(0, 0)),
+  ("timers/promises", NodeVersion(15, 0)),
+  ("tls", NodeVersion(0, 0)),
+  ("trace_events", NodeVersion(10, 0)),
+  ("tty", NodeVersion(0, 0)),
+  ("url", NodeVersion(0, 0)),
+  ("util", NodeVersion(0, 0)),
+  ("util/types", NodeVersion(15, 3)),
+  ("v8", NodeVersion(0, 0)),
+  ("vm", NodeVersion(0, 0)),
+  ("wasi", NodeVersion(13, 3)),
+  ("worker_threads", NodeVersion(10, 5)),
+  ("zlib", NodeVersion(0, 0)),
+];
+
+// the builtin modules that can only be required with the `node:` prefix
+const PREFIX_ONLY_BUILTINS: &[(&str, NodeVersion)] = &[
+  ("sea", NodeVersion(20, 12)),
+  ("sqlite", NodeVersion(22, 5)),
+  ("test", NodeVersion(18, 0)),
+  ("test/reporters", NodeVersion(19, 9)),
+];
+
+// the documented export names of the common builtin modules and the version they were added in,
+// names backported to older release lines are only reported from the line that added them
+const BUILTIN_EXPORTS: &[(&str, &[(&str, NodeVersion)])] = &[
+  (
+    "buffer",
+    &[
+      ("Blob", NodeVersion(15, 7)),
+      ("Buffer", NodeVersion(0, 0)),
+      ("File", NodeVersion(19, 2)),
+      ("INSPECT_MAX_BYTES", NodeVersion(0, 0)),
+      ("SlowBuffer", NodeVersion(0, 0)),
+      ("atob", NodeVersion(16, 0)),
+      ("btoa", NodeVersion(16, 0)),
+      ("constants", NodeVersion(8, 2)),
+      ("isAscii", NodeVersion(19, 6)),
+      ("isUtf8", NodeVersion(19, 4)),
+      ("kMaxLength", NodeVersion(0, 0)),
+      ("kStringMaxLength", NodeVersion(0, 0)),
+      ("resolveObjectURL", NodeVersion(16, 7)),
+      ("transcode", NodeVersion(7, 1)),
+    ],
+  ),
+  (
+    "child_process",
+    &[
+      ("ChildProcess", NodeVersion(0, 0)),
+      ("exec", NodeVersion(0, 0)),
+      ("execFile", NodeVersion(0, 0)),
+      ("execFileSync", NodeVersion(0, 0)),
+      ("execSync", NodeVersion(0, 0)),
+      ("fork", NodeVersion(0, 0)),
+      ("spawn", NodeVersion(0, 0)),
+      ("spawnSync", NodeVersion(0, 0)),
+    ],
+  ),
+  (
+    "events",
+    &[
+      ("EventEmitter", NodeVersion(0, 0)),
+      ("EventEmitterAsyncResource", NodeVersion(17, 4)),
+      ("addAbortListener", NodeVersion(20, 5)),
+      ("captureRejectionSymbol", NodeVersion(13, 4)),
+      ("captureRejections", NodeVersion(13, 4)),
+      ("defaultMaxListeners", NodeVersion(0, 0)),
+      ("errorMonitor", NodeVersion(13, 6)),
+      ("getEventListeners", NodeVersion(15, 2)),
+      ("getMaxListeners", NodeVersion(19, 9)),
+      ("listenerCount", NodeVersion(0, 0)),
+      ("on", NodeVersion(13, 6)),
+      ("once", NodeVersion(11, 13)),
+      ("setMaxListeners", NodeVersion(15, 4)),
+    ],
+  ),
+  (
+    "fs",
+    &[
+      ("Dir", NodeVersion(12, 12)),
+      ("Dirent", NodeVersion(10, 10)),
+      ("F_OK", NodeVersion(0, 0)),
+      ("R_OK", NodeVersion(0, 0)),
+      ("ReadStream", NodeVersion(0, 0)),
+      ("Stats", NodeVersion(0, 0)),
+      ("W_OK", NodeVersion(0, 0)),
+      ("WriteStream", NodeVersion(0, 0)),
+      ("X_OK", NodeVersion(0, 0)),
+      ("access", NodeVersion(0, 0)),
+      ("accessSync", NodeVersion(0, 0)),
+      ("appendFile", NodeVersion(0, 0)),
+      ("appendFileSync", NodeVersion(0, 0)),
+      ("chmod", NodeVersion(0, 0)),
+      ("chmodSync", NodeVersion(0, 0)),
+      ("chown", NodeVersion(0, 0)),
+      ("chownSync", NodeVersion(0, 0)),
+      ("close", NodeVersion(0, 0)),
+      ("closeSync", NodeVersion(0, 0)),
+      ("constants", NodeVersion(6, 3)),
+      ("copyFile", NodeVersion(8, 5)),
+      ("copyFileSync", NodeVersion(8, 5)),
+      ("cp", NodeVersion(16, 7)),
+      ("cpSync", NodeVersion(16, 7)),
+      ("createReadStream", NodeVersion(0, 0)),
+      ("createWriteStream", NodeVersion(0, 0)),
+      ("exists", NodeVersion(0, 0)),
+      ("existsSync", NodeVersion(0, 0)),
+      ("fchmod", NodeVersion(0, 0)),
+      ("fchmodSync", NodeVersion(0, 0)),
+      ("fchown", NodeVersion(0, 0)),
+      ("fchownSync", NodeVersion(0, 0)),
+      ("fdatasync", NodeVersion(0, 0)),
+      ("fdatasyncSync", NodeVersion(0, 0)),
+      ("fstat", NodeVersion(0, 0)),
+      ("fstatSync", NodeVersion(0, 0)),
+      ("fsync", NodeVersion(0, 0)),
+      ("fsyncSync", NodeVersion(0, 0)),
+      ("ftruncate", NodeVersion(0, 0)),
+      ("ftruncateSync", NodeVersion(0, 0)),
+      ("futimes", NodeVersion(0, 0)),
+      ("futimesSync", NodeVersion(0, 0)),
+      ("lchmod", NodeVersion(0, 0)),
+      ("lchmodSync", NodeVersion(0, 0)),
+      ("lchown", NodeVersion(0, 0)),
+      ("lchownSync", NodeVersion(0, 0)),
+      ("link", NodeVersion(0, 0)),
+      ("linkSync", NodeVersion(0, 0)),
+      ("lstat", NodeVersion(0, 0)),
+      ("lstatSync", NodeVersion(0, 0)),
+      ("lutimes", NodeVersion(14, 5)),
+      ("lutimesSync", NodeVersion(14, 5)),
+      ("mkdir", NodeVersion(0, 0)),
+      ("mkdirSync", NodeVersion(0, 0)),
+      ("mkdtemp", NodeVersion(5, 10)),
+      ("mkdtempSync", NodeVersion(5, 10)),
+      ("open", NodeVersion(0, 0)),
+      ("openAsBlob", NodeVersion(19, 8)),
+      ("openSync", NodeVersion(0, 0)),
+      ("opendir", NodeVersion(12, 12)),
+      ("opendirSync", NodeVersion(12, 12)),
+      ("promises", NodeVersion(10, 0)),
+      ("read", NodeVersion(0, 0)),
+      ("readFile", NodeVersion(0, 0)),
+      ("readFileSync", NodeVersion(0, 0)),
+      ("readSync", NodeVersion(0, 0)),
+      ("readdir", NodeVersion(0, 0)),
+      ("readdirSync", NodeVersion(0, 0)),
+      ("readlink", NodeVersion(0, 0)),
+      ("readlinkSync", NodeVersion(0, 0)),
+      ("readv", NodeVersion(13, 13)),
+      ("readvSync", NodeVersion(13, 13)),
+      ("realpath", NodeVersion(0, 0)),
+      ("realpathSync", NodeVersion(0, 0)),
+      ("rename", NodeVersion(0, 0)),
+      ("renameSync", NodeVersion(0, 0)),
+      ("rm", NodeVersion(14, 14)),
+      ("rmSync", NodeVersion(14, 14)),
+      ("rmdir", NodeVersion(0, 0)),
+      ("rmdirSync", NodeVersion(0, 0)),
+      ("stat", NodeVersion(0, 0)),
+      ("statSync", NodeVersion(0, 0)),
+      ("statfs", NodeVersion(19, 6)),
+      ("statfsSync", NodeVersion(19, 6)),
+      ("symlink", NodeVersion(0, 0)),
+      ("symlinkSync", NodeVersion(0, 0)),
+      ("truncate", NodeVersion(0, 0)),
+      ("truncateSync", NodeVersion(0, 0)),
+      ("unlink", NodeVersion(0, 0)),
+      ("unlinkSync", NodeVersion(0, 0)),
+      ("unwatchFile", NodeVersion(0, 0)),
+      ("utimes", NodeVersion(0, 0)),
+      ("utimesSync", NodeVersion(0, 0)),
+      ("watch", NodeVersion(0, 0)),
+      ("watchFile", NodeVersion(0, 0)),
+      ("write", NodeVersion(0, 0)),
+      ("writeFile", NodeVersion(0, 0)),
+      ("writeFileSync", NodeVersion(0, 0)),
+      ("writeSync", NodeVersion(0, 0)),
+      ("writev", NodeVersion(12, 9)),
+      ("writevSync", NodeVersion(12, 9)),
+    ],
+  ),
+  (
+    "os",
+    &[
+      ("EOL", NodeVersion(0, 0)),
+      ("arch", NodeVersion(0, 0)),
+      ("availableParallelism", NodeVersion(19, 4)),
+      ("constants", NodeVersion(6, 3)),
+      ("cpus", NodeVersion(0, 0)),
+      ("devNull", NodeVersion(16, 3)),
+      ("endianness", NodeVersion(0, 0)),
+      ("freemem", NodeVersion(0, 0)),
+      ("getPriority", NodeVersion(10, 10)),
+      ("homedir", NodeVersion(2, 3)),
+      ("hostname", NodeVersion(0, 0)),
+      ("loadavg", NodeVersion(0, 0)),
+      ("machine", NodeVersion(18, 9)),
+      ("networkInterfaces", NodeVersion(0, 0)),
+      ("platform", NodeVersion(0, 0)),
+      ("release", NodeVersion(0, 0)),
+      ("setPriority", NodeVersion(10, 10)),
+      ("tmpdir", NodeVersion(0, 0)),
+      ("totalmem", NodeVersion(0, 0)),
+      ("type", NodeVersion(0, 0)),
+      ("uptime", NodeVersion(0, 0)),
+      ("userInfo", NodeVersion(6, 0)),
+      ("version", NodeVersion(13, 11)),
+    ],
+  ),
+  (
+    "path",
+    &[
+      ("basename", NodeVersion(0, 0)),
+      ("delimiter", NodeVersion(0, 0)),
+      ("dirname", NodeVersion(0, 0)),
+      ("extname", NodeVersion(0, 0)),
+      ("format", NodeVersion(0, 0)),
+      ("isAbsolute", NodeVersion(0, 0)),
+      ("join", NodeVersion(0, 0)),
+      ("matchesGlob", NodeVersion(22, 5)),
+      ("normalize", NodeVersion(0, 0)),
+      ("parse", NodeVersion(0, 0)),
+      ("posix", NodeVersion(0, 0)),
+      ("relative", NodeVersion(0, 0)),
+      ("resolve", NodeVersion(0, 0)),
+      ("sep", NodeVersion(0, 0)),
+      ("toNamespacedPath", NodeVersion(9, 0)),
+      ("win32", NodeVersion(0, 0)),
+    ],
+  ),
+  (
+    "querystring",
+    &[
+      ("decode", NodeVersion(0, 0)),
+      ("encode", NodeVersion(0, 0)),
+      ("escape", NodeVersion(0, 0)),
+      ("parse", NodeVersion(0, 0)),
+      ("stringify", NodeVersion(0, 0)),
+      ("unescape", NodeVersion(0, 0)),
+    ],
+  ),
+  ("string_decoder", &[("StringDecoder", NodeVersion(0, 0))]),
+  (
+    "timers",
+    &[
+      ("clearImmediate", NodeVersion(0, 0)),
+      ("clearInterval", NodeVersion(0, 0)),
+      ("clearTimeout", NodeVersion(0, 0)),
+      ("promises", NodeVersion(15, 0)),
+      ("setImmediate", NodeVersion(0, 0)),
+      ("setInterval", NodeVersion(0, 0)),
+      ("setTimeout", NodeVersion(0, 0)),
+    ],
+  ),
+  (
+    "url",
+    &[
+      ("URL", NodeVersion(7, 0)),
+      ("URLSearchParams", NodeVersion(7, 5)),
+      ("domainToASCII", NodeVersion(7, 4)),
+      ("domainToUnicode", NodeVersion(7, 4)),
+      ("fileURLToPath", NodeVersion(10, 12)),
+      ("format", NodeVersion(0, 0)),
+      ("parse", NodeVersion(0, 0)),
+      ("pathToFileURL", NodeVersion(10, 12)),
+      ("resolve", NodeVersion(0, 0)),
+      ("urlToHttpOptions", NodeVersion(15, 7)),
+    ],
+  ),
+  (
+    "util",
+    &[
+      ("TextDecoder", NodeVersion(8, 3)),
+      ("TextEncoder", NodeVersion(8, 3)),
+      ("callbackify", NodeVersion(8, 2)),
+      ("debug", NodeVersion(14, 9)),
+      ("debuglog", NodeVersion(0, 0)),
+      ("deprecate", NodeVersion(0, 0)),
+      ("format", NodeVersion(0, 0)),
+      ("formatWithOptions", NodeVersion(10, 0)),
+      ("getSystemErrorMap", NodeVersion(16, 0)),
+      ("getSystemErrorName", NodeVersion(9, 7)),
+      ("inherits", NodeVersion(0, 0)),
+      ("inspect", NodeVersion(0, 0)),
+      ("isArray", NodeVersion(0, 0)),
+      ("isDeepStrictEqual", NodeVersion(9, 0)),
+      ("parseArgs", NodeVersion(18, 3)),
+      ("promisify", NodeVersion(8, 0)),
+      ("stripVTControlCharacters", NodeVersion(16, 11)),
+      ("styleText", NodeVersion(21, 7)),
+      ("toUSVString", NodeVersion(16, 8)),
+      ("transferableAbortController", NodeVersion(18, 11)),
+      ("transferableAbortSignal", NodeVersion(18, 11)),
+      ("types", NodeVersion(10, 0)),
+    ],
+  ),
+];
+
+/// parse the `nodeVersion` option, `latest` means the latest node.
+pub fn parse_node_version(version: &str) -> Result<Option<NodeVersion>, String> {
+  if version.trim() == "latest" {
+    return Ok(None);
+  }
+  match NodeVersion::parse(version) {
+    Some(version) => Ok(Some(version)),
+    None => Err(format!("invalid node version: {}", version)),
+  }
+}
+
+/// classify a module specifier, `node_version` defaults to the latest node.
+pub fn classify_specifier(specifier: &str, node_version: Option<NodeVersion>, builtin_exports: bool) -> Specifier {
+  // no version means the latest node
+  let version = node_version.unwrap_or(NodeVersion(u32::MAX, u32::MAX));
+  let kind = get_specifier_kind(specifier, version);
+  let exports = if builtin_exports && kind == SpecifierKind::Builtin {
+    let name = specifier.strip_prefix("node:").unwrap_or(specifier);
+    BUILTIN_EXPORTS
+      .iter()
+      .find(|(builtin, _)| builtin.eq(&name))
+      .map(|(_, exports)| {
+        exports
+          .iter()
+          .filter(|(_, since)| version >= *since)
+          .map(|(name, _)| name.to_string())
+          .collect()
+      })
+  } else {
+    None
+  };
+  Specifier { kind, exports }
+}
+
+fn get_specifier_kind(specifier: &str, version: NodeVersion) -> SpecifierKind {
+  if specifier == "." || specifier == ".." || specifier.starts_with("./") || specifier.starts_with("../") {
+    return SpecifierKind::Relative;
+  }
+  if specifier.starts_with('/') || specifier.starts_with("file:") || is_windows_path(specifier) {
+    return SpecifierKind::Absolute;
+  }
+  let is_builtin = match specifier.strip_prefix("node:") {
+    Some(name) => {
+      version.supports_node_prefix()
+        && BUILTINS
+          .iter()
+          .chain(PREFIX_ONLY_BUILTINS)
+          .any(|(builtin, since)| builtin.eq(&name) && version >= *since)
+    }
+    None => BUILTINS
+      .iter()
+      .any(|(builtin, since)| builtin.eq(&specifier) && version >= *since),
+  };
+  if is_builtin {
+    SpecifierKind::Builtin
+  } else {
+    SpecifierKind::Bare
+  }
+}
+
+// C:\lib or C:/lib
+fn is_windows_path(specifier: &str) -> bool {
+  let bytes = specifier.as_bytes();
+  bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && (bytes[2] == b'\\' || bytes[2] == b'/')
+}
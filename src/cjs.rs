@@ -1,4 +1,8 @@
 use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
 use std::vec;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
@@ -9,32 +13,303 @@ pub enum IdentKind {
   Lit(Lit),
   Alias(String),
   Object(Vec<PropOrSpread>),
-  Class(Class),
+  Class(ClassDesc),
+  Array(Vec<Expr>),
   Fn(FnDesc),
   Reexport(String),
+  Builtin(String),
   Unkonwn,
 }
 
 #[derive(Clone, Debug)]
 pub struct FnDesc {
   stmts: Vec<Stmt>,
-  extends: Vec<String>,
+  params: Vec<Pat>,
+  is_arrow: bool,
+  extends: IndexMap<String, ValueKind>,
+  prototype: IndexMap<String, ValueKind>,
 }
 
+#[derive(Clone, Debug)]
+pub struct ClassDesc {
+  class: Class,
+  // static members added after the declaration, like `Foo.create = ...`
+  statics: IndexMap<String, ValueKind>,
+  // members added to the prototype, like `Foo.prototype.run = ...`
+  prototype: IndexMap<String, ValueKind>,
+}
+
+// a module function of a browserify bundle
+#[derive(Clone, Debug)]
+pub struct BundledModule {
+  desc: FnDesc,
+  // the ids of the bundled modules it requires, like `{ "./dep": 2 }`
+  deps: IndexMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReexportKind {
+  // module.exports = require("./lib")
+  Namespace,
+  // module.exports = require("./lib")(options)
+  Call,
+  // module.exports = require("./lib").default
+  Member,
+}
+
+// a module whose exports are reexported
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Reexport {
+  pub specifier: String,
+  pub kind: ReexportKind,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub member: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub args: Option<Vec<ReexportArg>>,
+}
+
+impl Reexport {
+  pub fn namespace(specifier: String) -> Self {
+    Reexport {
+      specifier,
+      kind: ReexportKind::Namespace,
+      member: None,
+      args: None,
+    }
+  }
+}
+
+impl fmt::Display for Reexport {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match (&self.kind, &self.member) {
+      (ReexportKind::Call, _) => write!(f, "{}()", self.specifier),
+      (ReexportKind::Member, Some(member)) => write!(f, "{}.{}", self.specifier, member),
+      _ => write!(f, "{}", self.specifier),
+    }
+  }
+}
+
+// the kind of an exported value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueKind {
+  Function,
+  Class,
+  Primitive,
+  Object,
+  Unknown,
+}
+
+// an argument passed to `module.exports` in call mode
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CallArg {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<CallArg>),
+  Object(IndexMap<String, CallArg>),
+}
+
+impl CallArg {
+  pub fn to_expr(&self) -> Expr {
+    match self {
+      CallArg::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+      CallArg::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+        span: DUMMY_SP,
+        value: *value,
+      })),
+      CallArg::Number(value) => Expr::Lit(Lit::Num(Number {
+        span: DUMMY_SP,
+        value: *value,
+        raw: None,
+      })),
+      CallArg::String(value) => Expr::Lit(Lit::Str(quote_str(value))),
+      CallArg::Array(values) => Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: values
+          .iter()
+          .map(|value| {
+            Some(ExprOrSpread {
+              spread: None,
+              expr: Box::new(value.to_expr()),
+            })
+          })
+          .collect(),
+      }),
+      CallArg::Object(props) => Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: props
+          .iter()
+          .map(|(key, value)| {
+            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+              key: PropName::Str(quote_str(key)),
+              value: Box::new(value.to_expr()),
+            })))
+          })
+          .collect(),
+      }),
+    }
+  }
+}
+
+// the binding of an export to another module, `imported` is `*` for the module namespace
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ExportSource {
+  pub specifier: String,
+  pub imported: String,
+}
+
+// the value of an argument passed to a reexported function, `null` if it is not a literal
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum ReexportArg {
+  String(String),
+  Number(ArgNumber),
+  Bool(bool),
+  Null,
+}
+
+// a finite number, stored by its bits so that equal numbers hash the same
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArgNumber(u64);
+
+impl ArgNumber {
+  // `-0` is stored as `0`, `NaN` and infinities have no JSON representation
+  pub fn new(value: f64) -> Option<Self> {
+    if !value.is_finite() {
+      return None;
+    }
+    let value = if value == 0.0 { 0.0 } else { value };
+    Some(ArgNumber(value.to_bits()))
+  }
+
+  pub fn value(&self) -> f64 {
+    f64::from_bits(self.0)
+  }
+}
+
+impl Serialize for ArgNumber {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(self.value())
+  }
+}
+
+// helpers emitted by bundlers and compilers to define exports,
+// with the indexes of the arguments they read
+#[derive(Clone, Debug)]
+pub enum ExportHelper {
+  // $parcel$export(exports, "foo", () => foo)
+  DefineGetter { target: usize, name: usize },
+  // _export(exports, { foo: function() { return foo } })
+  DefineGetters { target: usize, getters: usize },
+  // _export_star(require("./foo"), exports)
+  // $parcel$exportWildcard(module.exports, require("./foo"))
+  ExportStar { from: usize, to: usize },
+  // $parcel$defineInteropFlag(module.exports)
+  InteropFlag { target: usize },
+  // function __export(m) { for (var p in m) if (!exports.hasOwnProperty(p)) exports[p] = m[p] }
+  CopyToExports { from: usize },
+}
+
+// the max depth of nested local function calls to follow
+const MAX_CALL_DEPTH: usize = 4;
+
+// the max number of values a loop is unrolled for
+const MAX_LOOP_VALUES: usize = 64;
+
+// the max number of iterations unrolled across all loops, nested loops included
+pub const MAX_LOOP_ITERATIONS: usize = 256;
+
 pub struct CJSLexer {
   pub node_env: String,
   pub call_mode: bool,
+  pub call_args: Vec<ExprOrSpread>,
+  pub instantiate: bool,
   pub fn_returned: bool,
+  // walking a function called with `new`, only object returns replace the instance
+  pub constructing: bool,
+  pub call_depth: usize,
   pub exports_alias: IndexSet<String>,
   pub idents: IndexMap<String, IdentKind>,
   pub exports: IndexSet<String>,
-  pub reexports: IndexSet<String>,
+  pub reexports: IndexSet<Reexport>,
+  pub requires: IndexSet<String>,
+  pub sources: IndexMap<String, ExportSource>,
+  pub kinds: IndexMap<String, ValueKind>,
+  pub exports_kind: ValueKind,
+  pub prototype: IndexSet<String>,
+  pub opaque_instance: bool,
+  // the modules of the browserify bundle being walked, by id
+  pub bundle: Rc<IndexMap<String, BundledModule>>,
+  // the id of the bundled module being walked
+  pub bundle_module: Option<String>,
+  // the iterations left to unroll, shared by all sub lexers
+  pub loop_budget: Rc<Cell<usize>>,
 }
 
 impl CJSLexer {
   fn clear(&mut self) {
     self.exports.clear();
     self.reexports.clear();
+    self.sources.clear();
+    self.kinds.clear();
+    self.prototype.clear();
+  }
+
+  // add an export, tracking the module it is bound to if its value is a `require` call
+  fn use_export(&mut self, name: String, value: Option<&Expr>) {
+    let kind = value.map_or(ValueKind::Unknown, |value| self.as_value_kind(value));
+    self.kinds.insert(name.clone(), kind);
+    match value.and_then(|value| self.as_export_source(value)) {
+      Some(source) => {
+        self.sources.insert(name.clone(), source);
+      }
+      None => {
+        self.sources.shift_remove(&name);
+      }
+    }
+    self.exports.insert(name);
+  }
+
+  fn use_export_kind(&mut self, name: String, kind: ValueKind) {
+    self.sources.shift_remove(&name);
+    self.kinds.insert(name.clone(), kind);
+    self.exports.insert(name);
+  }
+
+  // require("./lib")
+  // require("./lib").foo
+  fn as_export_source(&self, expr: &Expr) -> Option<ExportSource> {
+    let source = if let Some(specifier) = self.as_reexport(expr) {
+      ExportSource {
+        specifier,
+        imported: "*".to_owned(),
+      }
+    } else {
+      match self.as_member_reexport(expr)? {
+        Reexport {
+          specifier,
+          member: Some(imported),
+          ..
+        } => ExportSource { specifier, imported },
+        _ => return None,
+      }
+    };
+    // modules of the same bundle are not dependencies
+    if self.as_bundled_module(&source.specifier).is_some() {
+      return None;
+    }
+    Some(source)
+  }
+
+  // the id of the bundled module that `require(specifier)` resolves to in a browserify bundle
+  fn as_bundled_module(&self, specifier: &str) -> Option<String> {
+    let module = self.bundle.get(self.bundle_module.as_ref()?)?;
+    let id = module.deps.get(specifier)?;
+    self.bundle.contains_key(id).then(|| id.to_owned())
   }
 
   fn reset(&mut self, expr: &Expr) {
@@ -42,37 +317,160 @@ impl CJSLexer {
       self.reset(expr);
       return;
     }
+    // return e.foo = 1, { a: e }
+    if let Expr::Seq(SeqExpr { exprs, .. }) = expr {
+      if let Some((last, rest)) = exprs.split_last() {
+        for expr in rest {
+          self.mark_expr(expr);
+          self.parse_expr(expr);
+        }
+        self.reset(last);
+      }
+      return;
+    }
+    // module.exports = exports = {}
+    if let Expr::Assign(assign) = expr {
+      if assign.op == AssignOp::Assign {
+        let alias = match &assign.left {
+          AssignTarget::Simple(SimpleAssignTarget::Ident(BindingIdent { id, .. }))
+            if id.sym.as_ref().eq("exports") || self.is_exports_ident(id.sym.as_ref()) =>
+          {
+            Some(id.sym.as_ref().to_owned())
+          }
+          _ => None,
+        };
+        if alias.is_none() {
+          self.mark_expr(expr);
+        }
+        self.reset(&assign.right);
+        if let Some(name) = alias {
+          self.use_exports_alias(name);
+        }
+        return;
+      }
+    }
+    // module.exports = exports
+    if self.is_exports_expr(expr) {
+      return;
+    }
+    if let Expr::Call(call) = expr {
+      // module.exports = Object.freeze(Foo)
+      if self.is_frozen_call(call) {
+        self.reset(&call.args[0].expr);
+        return;
+      }
+      // module.exports = Object.assign(function () {}, { foo: 'bar' })
+      if self.is_builtin_call(call, "Object.assign") && !call.args.is_empty() && self.as_obj(expr).is_none() {
+        self.reset(&call.args[0].expr);
+        for arg in &call.args[1..] {
+          self.use_spread_as_exports(&arg.expr);
+        }
+        return;
+      }
+    }
+    // `exports` and `this` still point to the replaced object
+    for name in self.exports_alias.clone() {
+      self.detach_exports_alias(&name);
+    }
+    if self.is_exports_ident("exports") {
+      self.detach_exports_alias("exports");
+    }
+    self.exports_kind = self.as_value_kind(expr);
+    self.opaque_instance = false;
     if let Some(reexport) = self.as_reexport(expr) {
       self.clear();
-      self.reexports.insert(reexport);
+      match self.as_bundled_module(&reexport) {
+        Some(id) => {
+          self.exports_kind = ValueKind::Object;
+          self.walk_bundled_module(self.bundle.clone(), &id);
+        }
+        None => {
+          self.reexports.insert(Reexport::namespace(reexport));
+        }
+      }
+    } else if let Some(reexport) = self.as_member_reexport(expr) {
+      self.clear();
+      if self.as_bundled_module(&reexport.specifier).is_none() {
+        self.reexports.insert(reexport);
+      }
+    } else if let Some((bundle, entry)) = get_browserify_bundle(expr) {
+      self.clear();
+      self.exports_kind = ValueKind::Object;
+      self.walk_bundled_module(Rc::new(bundle), &entry);
     } else if let Some(props) = self.as_obj(expr) {
       self.clear();
       self.use_object_as_exports(props);
-    } else if let Some(class) = self.as_class(expr) {
+    } else if let Some(desc) = self.as_class(expr) {
       self.clear();
-      for name in get_class_static_names(&class) {
-        self.exports.insert(name);
+      if self.call_mode {
+        // classes can't be called without `new`
+        let args = self.call_args.clone();
+        self.use_class_instance(&desc, &args);
+      } else {
+        for (name, kind) in self.get_class_statics(&desc) {
+          self.use_export_kind(name, kind);
+        }
       }
-    } else if let Some(FnDesc { stmts, extends }) = self.as_function(expr) {
+      self.prototype = self.get_class_prototype(&desc).into_keys().collect();
+    } else if let Some(desc) = self.as_function(expr) {
       self.clear();
+      let prototype = desc.prototype.clone();
       if self.call_mode {
-        self.walk_body(stmts, true);
+        let args = self.call_args.clone();
+        if self.instantiate {
+          self.walk_fn_instance(desc, &args);
+          self.use_prototype_exports(prototype.clone());
+        } else {
+          self.walk_fn_body(desc, None, &args, true);
+        }
+        // the returned value doesn't change what `module.exports` is
+        self.exports_kind = ValueKind::Function;
       } else {
-        for name in extends {
-          self.exports.insert(name);
+        for (name, kind) in desc.extends {
+          self.use_export_kind(name, kind);
         }
       }
+      self.prototype = prototype.into_keys().collect();
+    } else if let Expr::New(NewExpr { callee, args, .. }) = expr {
+      // module.exports = new Registry()
+      self.clear();
+      let args = args.clone().unwrap_or_default();
+      if let Some(desc) = self.as_class(callee) {
+        self.use_class_instance(&desc, &args);
+      } else if let Some(desc) = self.as_function(callee) {
+        let prototype = desc.prototype.clone();
+        self.walk_fn_instance(desc, &args);
+        self.use_prototype_exports(prototype);
+      } else {
+        // module.exports = new EventEmitter()
+        self.opaque_instance = true;
+      }
+      self.exports_kind = ValueKind::Object;
+    } else if let Some((exports, reexports)) = self.as_webpack_bundle(expr) {
+      self.clear();
+      self.exports_kind = ValueKind::Object;
+      self.exports = exports;
+      self.reexports = reexports;
     } else if let Expr::Call(call) = expr {
-      if call.args.len() == 0 {
-        if let Some(callee) = with_expr_callee(call) {
-          if let Some(reexport) = self.as_reexport(callee) {
-            self.clear();
-            self.reexports.insert(format!("{}()", reexport));
-          } else if let Some(FnDesc { stmts, .. }) = self.as_function(callee) {
-            self.walk_body(stmts, true);
-          }
+      if let Some(callee) = with_expr_callee(call) {
+        if let Some(specifier) = self.as_reexport(callee) {
+          self.clear();
+          self.reexports.insert(Reexport {
+            specifier,
+            kind: ReexportKind::Call,
+            member: None,
+            args: Some(call.args.iter().map(|arg| self.as_reexport_arg(arg)).collect()),
+          });
+        } else if let Some(desc) = self.as_function(callee) {
+          // module.exports = factory(require('dep'))
+          self.walk_fn_call(desc, &call.args, true);
+        } else {
+          self.clear();
         }
       }
+    } else {
+      // module.exports = Module
+      self.clear();
     }
   }
 
@@ -81,70 +479,73 @@ impl CJSLexer {
       self.mark_ident(name, expr);
       return;
     }
-    match expr {
-      Expr::Lit(lit) => {
-        self.idents.insert(name.into(), IdentKind::Lit(lit.clone()));
-      }
-      Expr::Ident(id) => {
-        let conflict = if let Some(val) = self.idents.get(id.sym.as_ref()) {
-          if let IdentKind::Alias(rename) = val {
-            rename.eq(name)
-          } else {
-            false
-          }
-        } else {
-          false
-        };
-        if !conflict {
-          self
-            .idents
-            .insert(name.into(), IdentKind::Alias(id.sym.as_ref().into()));
-        }
-      }
-      Expr::Call(call) => {
-        if let Some(file) = is_require_call(&call) {
-          self.idents.insert(name.into(), IdentKind::Reexport(file));
-        }
-      }
-      Expr::Object(obj) => {
-        self.idents.insert(name.into(), IdentKind::Object(obj.props.clone()));
-      }
-      Expr::Class(ClassExpr { class, .. }) => {
-        self
-          .idents
-          .insert(name.into(), IdentKind::Class(class.as_ref().clone()));
-      }
-      Expr::Arrow(arrow) => {
-        self.idents.insert(
-          name.into(),
-          IdentKind::Fn(FnDesc {
-            stmts: get_arrow_body_as_stmts(&arrow),
-            extends: vec![],
-          }),
-        );
+    if let Expr::Ident(id) = expr {
+      // avoid alias cycles like `a = a` or `a = b; b = a`
+      let id = id.sym.as_ref();
+      let conflict = match self.idents.get(id) {
+        Some(IdentKind::Alias(rename)) => rename.eq(name),
+        _ => id.eq(name),
+      };
+      if conflict {
+        return;
       }
-      Expr::Fn(FnExpr { function, .. }) => {
-        if let Function { body: Some(body), .. } = function.as_ref() {
-          self.idents.insert(
-            name.into(),
-            IdentKind::Fn(FnDesc {
-              stmts: body.stmts.clone(),
-              extends: vec![],
-            }),
-          );
-        };
+    }
+    if let Some(kind) = self.as_ident_kind(expr) {
+      self.idents.insert(name.into(), kind);
+    }
+  }
+
+  fn as_ident_kind(&self, expr: &Expr) -> Option<IdentKind> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_ident_kind(expr),
+      Expr::Lit(lit) => Some(IdentKind::Lit(lit.clone())),
+      Expr::Ident(id) => Some(IdentKind::Alias(id.sym.as_ref().into())),
+      Expr::Call(call) => match self.is_require_call(call) {
+        Some(specifier) => Some(IdentKind::Reexport(specifier)),
+        // var obj = Object.create(null)
+        None => self.as_obj(expr).map(IdentKind::Object),
+      },
+      Expr::Object(obj) => Some(IdentKind::Object(obj.props.clone())),
+      Expr::Array(_) => Some(self.as_array(expr).map_or(IdentKind::Unkonwn, IdentKind::Array)),
+      Expr::Class(ClassExpr { class, .. }) => Some(IdentKind::Class(get_class_desc(class))),
+      Expr::Arrow(arrow) => Some(IdentKind::Fn(get_arrow_fn_desc(arrow))),
+      Expr::Fn(FnExpr { function, .. }) => get_fn_desc(function).map(IdentKind::Fn),
+      // var __exportStar = (this && this.__exportStar) || function (m, exports) { ... }
+      Expr::Bin(BinExpr {
+        op: BinaryOp::LogicalOr,
+        left,
+        right,
+        ..
+      }) if is_this_helper_check(left) => self.as_ident_kind(right),
+      // var __createBinding = Object.create ? function (o, m, k, k2) { ... } : function (o, m, k, k2) { ... }
+      Expr::Cond(CondExpr { cons, alt, .. }) if self.as_function(cons).is_some() && self.as_function(alt).is_some() => {
+        self.as_ident_kind(cons)
       }
       Expr::Member(_) => {
         if is_member_member(expr, "process", "env", "NODE_ENV") {
-          self
-            .idents
-            .insert(name.into(), IdentKind::Lit(Lit::Str(quote_str(self.node_env.as_str()))));
+          Some(IdentKind::Lit(Lit::Str(quote_str(self.node_env.as_str()))))
+        } else {
+          // var defineProperty = Object.defineProperty
+          self.as_builtin(expr).map(IdentKind::Builtin)
         }
       }
-      _ => {
-        self.idents.insert(name.into(), IdentKind::Unkonwn);
-      }
-    };
+      _ => Some(IdentKind::Unkonwn),
+    }
+  }
+
+  // the value of a call argument as seen by the param `name` of the called function
+  fn as_arg_kind(&self, name: &str, expr: &Expr) -> Option<IdentKind> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_arg_kind(name, expr),
+      Expr::Ident(id) => match self.idents.get(id.sym.as_ref()) {
+        Some(IdentKind::Alias(alias)) => self.as_arg_kind(name, &Expr::Ident(quote_ident(alias))),
+        Some(kind) => Some(kind.clone()),
+        // a global like `module` passed to a param with the same name
+        None if id.sym.as_ref().eq(name) => None,
+        None => Some(IdentKind::Alias(id.sym.as_ref().into())),
+      },
+      _ => Some(self.as_ident_kind(expr).unwrap_or(IdentKind::Unkonwn)),
+    }
   }
 
   fn as_str(&self, expr: &Expr) -> Option<String> {
@@ -165,15 +566,121 @@ impl CJSLexer {
           return Some(self.node_env.to_owned());
         }
       }
+      Expr::Unary(UnaryExpr {
+        op: UnaryOp::TypeOf,
+        arg,
+        ..
+      }) => return self.as_typeof(arg),
       _ => {}
     };
     None
   }
 
+  // exports.foo = function () {} => function
+  fn as_value_kind(&self, expr: &Expr) -> ValueKind {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_value_kind(expr),
+      // exports.foo = exports.bar = 'baz'
+      Expr::Assign(AssignExpr { right, .. }) => self.as_value_kind(right),
+      Expr::Cond(CondExpr { test, cons, alt, .. }) => match self.as_bool(test) {
+        Some(true) => self.as_value_kind(cons),
+        Some(false) => self.as_value_kind(alt),
+        None => {
+          let kind = self.as_value_kind(cons);
+          if kind == self.as_value_kind(alt) {
+            kind
+          } else {
+            ValueKind::Unknown
+          }
+        }
+      },
+      Expr::Lit(Lit::Regex(_)) | Expr::Array(_) | Expr::New(_) => ValueKind::Object,
+      Expr::Lit(_) | Expr::Tpl(_) | Expr::Unary(_) | Expr::Update(_) => ValueKind::Primitive,
+      Expr::Bin(BinExpr { op, .. })
+        if !matches!(
+          op,
+          BinaryOp::LogicalOr | BinaryOp::LogicalAnd | BinaryOp::NullishCoalescing
+        ) =>
+      {
+        ValueKind::Primitive
+      }
+      // obj[key]
+      Expr::Member(MemberExpr { obj, prop, .. }) if self.as_obj(obj).is_some() => {
+        match (self.as_obj(obj), self.as_member_prop_name(prop)) {
+          (Some(props), Some(name)) => self
+            .get_object_kinds(props)
+            .get(&name)
+            .copied()
+            .unwrap_or(ValueKind::Unknown),
+          _ => ValueKind::Unknown,
+        }
+      }
+      Expr::Ident(id) if id.sym.as_ref() == "undefined" && !self.idents.contains_key("undefined") => {
+        ValueKind::Primitive
+      }
+      Expr::Ident(id) => match self.idents.get(id.sym.as_ref()) {
+        Some(IdentKind::Lit(lit)) => self.as_value_kind(&Expr::Lit(lit.clone())),
+        Some(IdentKind::Alias(id)) => self.as_value_kind(&Expr::Ident(quote_ident(id))),
+        Some(IdentKind::Object(_)) => ValueKind::Object,
+        Some(IdentKind::Class(_)) => ValueKind::Class,
+        Some(IdentKind::Fn(_) | IdentKind::Builtin(_)) => ValueKind::Function,
+        _ => ValueKind::Unknown,
+      },
+      _ => {
+        if self.as_class(expr).is_some() {
+          ValueKind::Class
+        } else if self.as_function(expr).is_some() {
+          ValueKind::Function
+        } else if self.as_obj(expr).is_some() {
+          ValueKind::Object
+        } else {
+          ValueKind::Unknown
+        }
+      }
+    }
+  }
+
+  // typeof exports === 'object'
+  // typeof module !== 'undefined'
+  // typeof define === 'function'
+  fn as_typeof(&self, expr: &Expr) -> Option<String> {
+    let value = match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => return self.as_typeof(expr),
+      _ if self.is_exports_expr(expr) || self.is_module_expr(expr) => "object",
+      Expr::Lit(Lit::Str(_)) => "string",
+      Expr::Lit(Lit::Num(_)) => "number",
+      Expr::Lit(Lit::Bool(_)) => "boolean",
+      Expr::Lit(Lit::Null(_)) | Expr::Object(_) => "object",
+      Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_) => "function",
+      Expr::Ident(id) => match self.idents.get(id.sym.as_ref()) {
+        Some(IdentKind::Lit(lit)) => return self.as_typeof(&Expr::Lit(lit.clone())),
+        Some(IdentKind::Alias(id)) => return self.as_typeof(&Expr::Ident(quote_ident(id))),
+        Some(IdentKind::Object(_)) => "object",
+        Some(IdentKind::Class(_) | IdentKind::Fn(_) | IdentKind::Builtin(_)) => "function",
+        Some(_) => return None,
+        // globals in node
+        None => match id.sym.as_ref() {
+          "require" => "function",
+          "process" | "global" | "globalThis" => "object",
+          "define" | "window" | "self" | "document" => "undefined",
+          _ => return None,
+        },
+      },
+      _ => return None,
+    };
+    Some(value.to_owned())
+  }
+
   fn as_num(&self, expr: &Expr) -> Option<f64> {
     match expr {
       Expr::Paren(ParenExpr { expr, .. }) => return self.as_num(expr),
       Expr::Lit(Lit::Num(Number { value, .. })) => return Some(*value),
+      // -1
+      Expr::Unary(UnaryExpr {
+        op: UnaryOp::Minus,
+        arg,
+        ..
+      }) => return self.as_num(arg).map(|value| -value),
       Expr::Ident(id) => {
         if let Some(value) = self.idents.get(id.sym.as_ref()) {
           match value {
@@ -238,14 +745,132 @@ impl CJSLexer {
         }
         None
       }
+      Expr::Call(call) => {
+        let arg = call.args.first().map(|arg| arg.expr.as_ref());
+        if self.is_frozen_call(call) {
+          // Object.freeze({ foo: 'bar' })
+          self.as_obj(arg?)
+        } else if self.is_builtin_call(call, "Object.create") {
+          // Object.create(null)
+          match arg {
+            Some(Expr::Lit(Lit::Null(_))) => Some(vec![]),
+            _ => None,
+          }
+        } else if self.is_builtin_call(call, "Object.assign") && !call.args.is_empty() {
+          // Object.assign({}, require('./base'), { foo: 'bar' })
+          let target = arg?;
+          if self.as_function(target).is_some() || self.as_class(target).is_some() {
+            return None;
+          }
+          Some(
+            call
+              .args
+              .iter()
+              .filter(|arg| arg.spread.is_none())
+              .map(|arg| {
+                PropOrSpread::Spread(SpreadElement {
+                  dot3_token: DUMMY_SP,
+                  expr: arg.expr.clone(),
+                })
+              })
+              .collect(),
+          )
+        } else {
+          None
+        }
+      }
       _ => None,
     }
   }
 
+  // ['get', 'post']
+  // Object.keys({ get: 1, post: 2 })
+  fn as_array(&self, expr: &Expr) -> Option<Vec<Expr>> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_array(expr),
+      Expr::Array(ArrayLit { elems, .. }) => elems
+        .iter()
+        .map(|elem| match elem {
+          Some(ExprOrSpread { spread: None, expr }) => Some(expr.as_ref().clone()),
+          _ => None,
+        })
+        .collect(),
+      Expr::Ident(id) => match self.idents.get(id.sym.as_ref()) {
+        Some(IdentKind::Array(elems)) => Some(elems.clone()),
+        Some(IdentKind::Alias(id)) => self.as_array(&Expr::Ident(quote_ident(id))),
+        _ => None,
+      },
+      Expr::Call(call) if self.is_builtin_call(call, "Object.keys") => Some(
+        self
+          .as_obj_keys(call.args.first()?.expr.as_ref())?
+          .iter()
+          .map(|key| Expr::Lit(Lit::Str(quote_str(key))))
+          .collect(),
+      ),
+      _ => None,
+    }
+  }
+
+  // the keys of an object whose shape is fully known
+  fn as_obj_keys(&self, expr: &Expr) -> Option<Vec<String>> {
+    let props = self.as_obj(expr)?;
+    if props.iter().any(|prop| matches!(prop, PropOrSpread::Spread(_))) {
+      return None;
+    }
+    Some(self.get_object_kinds(props).into_keys().collect())
+  }
+
+  // ['get', 'post'].forEach(function (method) { ... })
+  fn get_for_each_call(&self, call: &CallExpr) -> Option<(Vec<Expr>, FnDesc)> {
+    let Some(Expr::Member(MemberExpr { obj, prop, .. })) = with_expr_callee(call) else {
+      return None;
+    };
+    if get_prop_name(prop).as_deref() != Some("forEach") {
+      return None;
+    }
+    let desc = self.as_function(call.args.first()?.expr.as_ref())?;
+    Some((self.as_array(obj)?, desc))
+  }
+
+  // for (const key of ['a', 'b']) { ... }
+  // walk the loop body with `key` bound to each value
+  // takes the iterations of a loop from the unroll budget
+  fn use_loop_budget(&self, iterations: usize) -> bool {
+    let budget = self.loop_budget.get();
+    if iterations > MAX_LOOP_VALUES || iterations > budget {
+      return false;
+    }
+    self.loop_budget.set(budget - iterations);
+    true
+  }
+
+  fn walk_loop_body(&mut self, name: &str, values: Vec<Expr>, body: &Stmt) {
+    if !self.use_loop_budget(values.len()) {
+      return;
+    }
+    for value in values {
+      let mut lexer = self.new_sub_lexer();
+      lexer.exports_alias.shift_remove(name);
+      let kind = self.as_ident_kind(&value).unwrap_or(IdentKind::Unkonwn);
+      lexer.idents.insert(name.to_owned(), kind);
+      self.walk_with(lexer, vec![body.clone()], false);
+    }
+  }
+
+  // Object.freeze(obj)
+  // Object.seal(obj)
+  // Object.preventExtensions(obj)
+  fn is_frozen_call(&self, call: &CallExpr) -> bool {
+    !call.args.is_empty()
+      && (self.is_builtin_call(call, "Object.freeze")
+        || self.is_builtin_call(call, "Object.seal")
+        || self.is_builtin_call(call, "Object.preventExtensions"))
+  }
+
   fn as_reexport(&self, expr: &Expr) -> Option<String> {
     match expr {
       Expr::Paren(ParenExpr { expr, .. }) => return self.as_reexport(expr),
-      Expr::Call(call) => is_require_call(&call),
+      Expr::Call(call) => self.is_require_call(call),
       Expr::Ident(id) => {
         if let Some(value) = self.idents.get(id.sym.as_ref()) {
           match value {
@@ -260,10 +885,140 @@ impl CJSLexer {
     }
   }
 
-  fn as_class(&self, expr: &Expr) -> Option<Class> {
+  // require("./lib").default
+  fn as_member_reexport(&self, expr: &Expr) -> Option<Reexport> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_member_reexport(expr),
+      Expr::Member(MemberExpr { obj, prop, .. }) => Some(Reexport {
+        specifier: self.as_reexport(obj)?,
+        kind: ReexportKind::Member,
+        member: Some(self.as_member_prop_name(prop)?),
+        args: None,
+      }),
+      _ => None,
+    }
+  }
+
+  fn as_reexport_arg(&self, arg: &ExprOrSpread) -> ReexportArg {
+    if arg.spread.is_some() {
+      return ReexportArg::Null;
+    }
+    let expr = arg.expr.as_ref();
+    if let Some(value) = self.as_str(expr) {
+      ReexportArg::String(value)
+    } else if let Some(value) = self.as_num(expr) {
+      ArgNumber::new(value).map_or(ReexportArg::Null, ReexportArg::Number)
+    } else if let Some(value) = self.as_bool(expr) {
+      ReexportArg::Bool(value)
+    } else {
+      ReexportArg::Null
+    }
+  }
+
+  // resolve the name of an ident through its aliases
+  fn resolve_ident_name(&self, name: &str) -> String {
+    if let Some(IdentKind::Alias(id)) = self.idents.get(name) {
+      return self.resolve_ident_name(id);
+    }
+    name.to_owned()
+  }
+
+  // match:
+  // Object.defineProperty
+  // Reflect.defineProperty
+  // require
+  // (0, Object.defineProperty)
+  // var O = Object; O.defineProperty
+  // var defineProperty = Object.defineProperty; defineProperty
+  // var { defineProperty } = Object; defineProperty
+  fn as_builtin(&self, expr: &Expr) -> Option<String> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_builtin(expr),
+      Expr::Seq(SeqExpr { exprs, .. }) => exprs.last().and_then(|expr| self.as_builtin(expr)),
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+      }) => {
+        // module.require
+        if prop.sym.as_ref().eq("require") && self.is_module_expr(obj) {
+          return Some("require".to_owned());
+        }
+        if let Expr::Ident(obj) = obj.as_ref() {
+          let obj_name = self.resolve_ident_name(obj.sym.as_ref());
+          if !self.idents.contains_key(obj_name.as_str()) && (obj_name.eq("Object") || obj_name.eq("Reflect")) {
+            return Some(format!("{}.{}", obj_name, prop.sym.as_ref()));
+          }
+        }
+        None
+      }
+      Expr::Ident(id) => match self.idents.get(id.sym.as_ref()) {
+        Some(IdentKind::Builtin(name)) => Some(name.to_owned()),
+        Some(IdentKind::Alias(id)) => self.as_builtin(&Expr::Ident(quote_ident(id))),
+        Some(_) => None,
+        None => match id.sym.as_ref() {
+          "require" | "module" => Some(id.sym.as_ref().to_owned()),
+          // webpack keeps node's require as `__non_webpack_require__`
+          "__non_webpack_require__" => Some("require".to_owned()),
+          _ => None,
+        },
+      },
+      _ => None,
+    }
+  }
+
+  // module
+  // function setup(m) { ... }; setup(module)
+  fn is_module_expr(&self, expr: &Expr) -> bool {
+    self.as_builtin(expr).as_deref() == Some("module")
+  }
+
+  // module.exports
+  fn is_module_exports_expr(&self, expr: &Expr) -> bool {
+    if let Expr::Member(MemberExpr { obj, prop, .. }) = expr {
+      return self.is_module_expr(obj) && get_prop_name(prop).as_deref() == Some("exports");
+    }
+    false
+  }
+
+  fn is_builtin_call(&self, call: &CallExpr, name: &str) -> bool {
+    if let Some(callee) = with_expr_callee(call) {
+      return self.as_builtin(callee).as_deref() == Some(name);
+    }
+    false
+  }
+
+  // require('lib')
+  // var r = require; r('lib')
+  // module.require('lib')
+  // require.call(null, 'lib')
+  // require.apply(null, ['lib'])
+  fn is_require_call(&self, call: &CallExpr) -> Option<String> {
+    if self.is_builtin_call(call, "require") {
+      return self.as_str(call.args.first()?.expr.as_ref());
+    }
+    if let Some(Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(prop),
+      ..
+    })) = with_expr_callee(call)
+    {
+      if self.as_builtin(obj).as_deref() == Some("require") {
+        let arg = call.args.get(1)?.expr.as_ref();
+        return match prop.sym.as_ref() {
+          "call" => self.as_str(arg),
+          "apply" => self.as_str(self.as_array(arg)?.first()?),
+          _ => None,
+        };
+      }
+    }
+    None
+  }
+
+  fn as_class(&self, expr: &Expr) -> Option<ClassDesc> {
     match expr {
       Expr::Paren(ParenExpr { expr, .. }) => return self.as_class(expr),
-      Expr::Class(ClassExpr { class, .. }) => Some(class.as_ref().clone()),
+      Expr::Class(ClassExpr { class, .. }) => Some(get_class_desc(class)),
       Expr::Ident(id) => {
         if let Some(value) = self.idents.get(id.sym.as_ref()) {
           match value {
@@ -281,16 +1036,8 @@ impl CJSLexer {
   fn as_function(&self, expr: &Expr) -> Option<FnDesc> {
     match expr {
       Expr::Paren(ParenExpr { expr, .. }) => return self.as_function(expr),
-      Expr::Fn(FnExpr { function, .. }) => {
-        if let Function { body: Some(body), .. } = function.as_ref() {
-          Some(FnDesc {
-            stmts: body.stmts.clone(),
-            extends: vec![],
-          })
-        } else {
-          None
-        }
-      }
+      Expr::Fn(FnExpr { function, .. }) => get_fn_desc(function),
+      Expr::Arrow(arrow) => Some(get_arrow_fn_desc(arrow)),
       Expr::Ident(id) => {
         if let Some(value) = self.idents.get(id.sym.as_ref()) {
           match value {
@@ -305,36 +1052,595 @@ impl CJSLexer {
     }
   }
 
+  // { foo: 'bar' }
+  // { [KEY]: 'bar' }
+  // { ['foo']: 'bar' }
+  // { 1: 'bar' }
+  fn as_prop_name(&self, name: &PropName) -> Option<String> {
+    match name {
+      PropName::Computed(ComputedPropName { expr, .. }) => self
+        .as_str(expr)
+        .or_else(|| self.as_num(expr).map(|value| value.to_string())),
+      PropName::Num(Number { value, .. }) => Some(value.to_string()),
+      _ => stringify_prop_name(name),
+    }
+  }
+
   fn use_object_as_exports(&mut self, props: Vec<PropOrSpread>) {
     for prop in props {
       match prop {
         PropOrSpread::Prop(prop) => {
-          let name = match prop.as_ref() {
-            Prop::Shorthand(id) => Some(id.sym.as_ref().to_owned()),
-            Prop::KeyValue(KeyValueProp { key, .. }) => stringify_prop_name(key),
-            Prop::Method(MethodProp { key, .. }) => stringify_prop_name(key),
-            _ => None,
+          let shorthand;
+          let (name, value) = match prop.as_ref() {
+            Prop::Shorthand(id) => {
+              shorthand = Expr::Ident(id.clone());
+              (Some(id.sym.as_ref().to_owned()), Some(&shorthand))
+            }
+            Prop::KeyValue(KeyValueProp { key, value }) => (self.as_prop_name(key), Some(value.as_ref())),
+            Prop::Method(MethodProp { key, function }) => {
+              shorthand = Expr::Fn(FnExpr {
+                ident: None,
+                function: function.clone(),
+              });
+              (self.as_prop_name(key), Some(&shorthand))
+            }
+            Prop::Getter(GetterProp { key, .. }) => (self.as_prop_name(key), None),
+            Prop::Setter(SetterProp { key, .. }) => (self.as_prop_name(key), None),
+            _ => (None, None),
           };
           if let Some(name) = name {
-            self.exports.insert(name);
+            self.use_export(name, value);
           }
         }
-        PropOrSpread::Spread(SpreadElement { expr, .. }) => match expr.as_ref() {
-          Expr::Ident(_) => {
-            if let Some(props) = self.as_obj(expr.as_ref()) {
-              self.use_object_as_exports(props);
+        PropOrSpread::Spread(SpreadElement { expr, .. }) => self.use_spread_as_exports(&expr),
+      }
+    }
+  }
+
+  // { ...obj }
+  // { ...require('lib') }
+  // { ...fn() }
+  // { ...(cond ? a : b) }
+  // { ...Object.assign({}, a, b) }
+  fn use_spread_as_exports(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.use_spread_as_exports(expr),
+      Expr::Cond(CondExpr { test, cons, alt, .. }) => {
+        if self.is_true(test) {
+          self.use_spread_as_exports(cons)
+        } else {
+          self.use_spread_as_exports(alt)
+        }
+      }
+      Expr::Call(call) => {
+        if let Some(reexport) = self.is_require_call(call) {
+          self.reexports.insert(Reexport::namespace(reexport));
+        } else if self.is_builtin_call(call, "Object.assign") {
+          for arg in &call.args {
+            self.use_spread_as_exports(&arg.expr);
+          }
+        } else if let Some(desc) = with_expr_callee(call).and_then(|callee| self.as_function(callee)) {
+          let (exports, reexports) = self.get_returned_exports(desc, &call.args);
+          self.exports.extend(exports);
+          self.reexports.extend(reexports);
+        }
+      }
+      _ => {
+        if let Some(props) = self.as_obj(expr) {
+          self.use_object_as_exports(props);
+        } else if let Some(reexport) = self.as_reexport(expr) {
+          self.reexports.insert(Reexport::namespace(reexport));
+        }
+      }
+    }
+  }
+
+  // Object.defineProperty(exports, 'foo', { value: 'bar' })
+  // Object.defineProperty(module.exports, 'foo', { get() { return 'bar' } })
+  // Object.defineProperty(module, 'exports', { value: { foo: 'bar' }})
+  fn define_property(&mut self, obj: &Expr, name: Option<String>, descriptor: &Expr) {
+    let is_module = self.is_module_expr(obj);
+    let is_exports = self.is_exports_expr(obj);
+
+    let mut with_value_or_getter = false;
+    let mut with_value: Option<Expr> = None;
+    if let Some(props) = self.as_obj(descriptor) {
+      for prop in props {
+        if let PropOrSpread::Prop(prop) = prop {
+          let key = match prop.as_ref() {
+            Prop::KeyValue(KeyValueProp { key, value, .. }) => {
+              let key = stringify_prop_name(key);
+              match key.as_deref() {
+                Some("value") => with_value = Some(value.as_ref().clone()),
+                // get: function () { return require('./lib') }
+                Some("get") => with_value = self.as_function(value).and_then(|desc| get_returned_expr(&desc.stmts)),
+                _ => {}
+              }
+              key
             }
-            if let Some(reexport) = self.as_reexport(expr.as_ref()) {
-              self.reexports.insert(reexport);
+            // get() { return require('./lib') }
+            Prop::Method(MethodProp { key, function }) => {
+              let key = stringify_prop_name(key);
+              if key.as_deref() == Some("get") {
+                with_value = get_fn_desc(function).and_then(|desc| get_returned_expr(&desc.stmts));
+              }
+              key
             }
-          }
-          Expr::Call(call) => {
-            if let Some(reexport) = is_require_call(call) {
-              self.reexports.insert(reexport);
+            _ => None,
+          };
+          if let Some(key) = key {
+            if key.eq("value") || key.eq("get") {
+              with_value_or_getter = true;
+              break;
             }
           }
-          _ => {}
+        }
+      }
+    }
+    if is_exports && with_value_or_getter {
+      if let Some(name) = name {
+        self.use_export(name, with_value.as_ref());
+      }
+    } else if is_module && name.as_deref() == Some("exports") {
+      if let Some(expr) = with_value {
+        self.reset(&expr);
+      }
+    }
+  }
+
+  // define([...], function () { ... })
+  fn is_amd_define_call(&self, call: &CallExpr) -> bool {
+    match with_expr_callee(call) {
+      Some(Expr::Ident(id)) => {
+        id.sym.as_ref().eq("define") && !self.idents.contains_key("define") && !call.args.is_empty()
+      }
+      _ => false,
+    }
+  }
+
+  // exports.__defineGetter__('foo', function() { return 'bar' })
+  fn is_exports_define_getter_call(&self, call: &CallExpr) -> Option<String> {
+    if let Some(Expr::Member(MemberExpr { obj, prop, .. })) = with_expr_callee(call) {
+      if self.is_exports_expr(obj) && get_prop_name(prop).as_deref() == Some("__defineGetter__") {
+        return call.args.first().and_then(|arg| self.as_str(&arg.expr));
+      }
+    }
+    None
+  }
+
+  fn get_webpack_define_call<'a>(&self, call: &'a CallExpr) -> Option<(&'a Expr, Vec<String>)> {
+    let (webpack_require, target, names) = get_webpack_define_call(call)?;
+    if !self.is_webpack_require(webpack_require) {
+      return None;
+    }
+    Some((target, names))
+  }
+
+  // e.r(t) => true
+  // e.d(t, { foo: () => n }) => n
+  // e.d(t, "foo", function () { return n }) => n
+  fn get_webpack_define_value(&self, call: &CallExpr, name: &str) -> Option<Expr> {
+    if call.args.len() == 1 {
+      return Some(Expr::Lit(Lit::Bool(Bool {
+        span: DUMMY_SP,
+        value: true,
+      })));
+    }
+    let getter = match call.args.get(1)?.expr.as_ref() {
+      Expr::Object(ObjectLit { props, .. }) => props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match prop.as_ref() {
+          Prop::KeyValue(KeyValueProp { key, value }) if stringify_prop_name(key).as_deref() == Some(name) => {
+            Some(value.as_ref())
+          }
+          _ => None,
+        },
+        _ => None,
+      })?,
+      Expr::Lit(Lit::Str(_)) => call.args.get(2)?.expr.as_ref(),
+      _ => return None,
+    };
+    self.as_function(getter).and_then(|desc| get_returned_expr(&desc.stmts))
+  }
+
+  // function __webpack_require__(moduleId) { ... }
+  // __webpack_require__.d = (exports, definition) => { ... }
+  // var e = { d: (t, r) => { ... }, o: (e, t) => { ... }, r: (e) => { ... } }
+  fn is_webpack_require(&self, id: &str) -> bool {
+    if id.eq("__webpack_require__") {
+      return true;
+    }
+    match self.idents.get(id) {
+      Some(IdentKind::Object(props)) => props.iter().any(|prop| match prop {
+        PropOrSpread::Prop(prop) => match prop.as_ref() {
+          Prop::KeyValue(KeyValueProp { key, .. }) => stringify_prop_name(key).as_deref() == Some("d"),
+          _ => false,
         },
+        _ => false,
+      }),
+      Some(IdentKind::Fn(FnDesc { extends, .. })) => extends.contains_key("d"),
+      _ => false,
+    }
+  }
+
+  // $parcel$export(module.exports, "foo", () => foo)
+  // _export(exports, { foo: function() { return foo } })
+  // _export_star(require("./foo"), exports)
+  // (0, _export_star._)(require("./foo"), exports)
+  fn as_export_helper(&self, callee: &Expr) -> Option<ExportHelper> {
+    match callee {
+      Expr::Paren(ParenExpr { expr, .. }) => self.as_export_helper(expr),
+      Expr::Seq(SeqExpr { exprs, .. }) => self.as_export_helper(exprs.last()?),
+      Expr::Ident(id) => {
+        if let Some(desc) = self.as_function(callee) {
+          return self.get_export_helper(&desc, 0);
+        }
+        match id.sym.as_ref() {
+          "$parcel$export" => Some(ExportHelper::DefineGetter { target: 0, name: 1 }),
+          "$parcel$exportWildcard" => Some(ExportHelper::ExportStar { from: 1, to: 0 }),
+          "$parcel$defineInteropFlag" => Some(ExportHelper::InteropFlag { target: 0 }),
+          "_export" => Some(ExportHelper::DefineGetters { target: 0, getters: 1 }),
+          "_export_star" => Some(ExportHelper::ExportStar { from: 0, to: 1 }),
+          _ => None,
+        }
+      }
+      // require("@swc/helpers/_/_export_star")._(require("./foo"), exports)
+      Expr::Member(MemberExpr { obj, .. }) => {
+        let specifier = self.as_reexport(obj)?;
+        let name = specifier.rsplit('/').next()?.split('.').next()?;
+        if specifier.starts_with("@swc/helpers/") && name.eq("_export_star") {
+          return Some(ExportHelper::ExportStar { from: 0, to: 1 });
+        }
+        None
+      }
+      _ => None,
+    }
+  }
+
+  // recognize a helper by its body, whatever its name is:
+  // function $parcel$export(e, n, v, s) { Object.defineProperty(e, n, { get: v, set: s, enumerable: true }) }
+  // function $parcel$defineInteropFlag(a) { Object.defineProperty(a, '__esModule', { value: true }) }
+  // function _export(target, all) { for (var name in all) Object.defineProperty(target, name, { enumerable: true, get: all[name] }) }
+  // function _export_star(from, to) { return Object.keys(from).forEach(function(k) { ... Object.defineProperty(to, k, ...) }), from }
+  // function __exportStar(m, exports) { for (var p in m) if (p !== "default") __createBinding(exports, m, p) }
+  // function __export(m) { for (var p in m) if (!exports.hasOwnProperty(p)) exports[p] = m[p] }
+  // var __copyProps = (to, from) => { for (let key of __getOwnPropNames(from)) __defProp(to, key, { get: () => from[key] }); return to }
+  // var __reExport = (target, mod) => __copyProps(target, mod, "default")
+  fn get_export_helper(&self, desc: &FnDesc, depth: usize) -> Option<ExportHelper> {
+    let param_index = |expr: &Expr| match expr {
+      Expr::Ident(id) => desc.params.iter().position(|param| match param {
+        Pat::Ident(BindingIdent { id: param, .. }) => param.sym.eq(&id.sym),
+        _ => false,
+      }),
+      _ => None,
+    };
+    // a helper does nothing but define the exports, apart from returning one of its params
+    let mut stmts = desc.stmts.iter().filter(|stmt| match stmt {
+      Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => param_index(arg).is_none(),
+      _ => true,
+    });
+    let mut stmt = stmts.next()?;
+    if stmts.next().is_some() {
+      return None;
+    }
+    // if (from && typeof from === "object") { ... }
+    loop {
+      stmt = match stmt {
+        Stmt::If(IfStmt { cons, alt: None, .. }) => cons.as_ref(),
+        Stmt::Block(BlockStmt { stmts, .. }) if stmts.len() == 1 => &stmts[0],
+        _ => break,
+      }
+    }
+    match stmt {
+      Stmt::Expr(ExprStmt { expr, .. }) | Stmt::Return(ReturnStmt { arg: Some(expr), .. }) => {
+        let exprs = match unwrap_paren(expr) {
+          Expr::Seq(SeqExpr { exprs, .. }) => exprs.iter().map(|expr| expr.as_ref()).collect(),
+          expr => vec![expr],
+        };
+        // __copyProps(target, mod, "default"), secondTarget && __copyProps(secondTarget, mod, "default")
+        let mut calls = vec![];
+        for expr in exprs {
+          match expr {
+            Expr::Call(call) => calls.push(call),
+            Expr::Bin(BinExpr {
+              op: BinaryOp::LogicalAnd,
+              right,
+              ..
+            }) if matches!(right.as_ref(), Expr::Call(_)) => {}
+            expr if param_index(expr).is_some() => {}
+            _ => return None,
+          }
+        }
+        let call = calls.first()?;
+        if self.is_builtin_call(call, "Object.defineProperty") && call.args.len() >= 3 {
+          let target = param_index(call.args[0].expr.as_ref())?;
+          let name = call.args[1].expr.as_ref();
+          if let Some(name) = param_index(name) {
+            return Some(ExportHelper::DefineGetter { target, name });
+          } else if self.as_str(name).as_deref() == Some("__esModule") {
+            return Some(ExportHelper::InteropFlag { target });
+          }
+        } else if let Some((from, to, _)) = self.get_keys_copy(call) {
+          return self.as_copy_helper(param_index(from)?, &to, param_index);
+        } else if depth < MAX_CALL_DEPTH {
+          // another helper called with the params of this one
+          let desc = self.as_function(with_expr_callee(call)?)?;
+          let arg_index = |index: usize| param_index(call.args.get(index)?.expr.as_ref());
+          return match self.get_export_helper(&desc, depth + 1)? {
+            ExportHelper::DefineGetter { target, name } => Some(ExportHelper::DefineGetter {
+              target: arg_index(target)?,
+              name: arg_index(name)?,
+            }),
+            ExportHelper::DefineGetters { target, getters } => Some(ExportHelper::DefineGetters {
+              target: arg_index(target)?,
+              getters: arg_index(getters)?,
+            }),
+            ExportHelper::ExportStar { from, to } => Some(ExportHelper::ExportStar {
+              from: arg_index(from)?,
+              to: arg_index(to)?,
+            }),
+            ExportHelper::InteropFlag { target } => Some(ExportHelper::InteropFlag {
+              target: arg_index(target)?,
+            }),
+            ExportHelper::CopyToExports { from } => Some(ExportHelper::CopyToExports { from: arg_index(from)? }),
+          };
+        }
+        None
+      }
+      // for (var name in all) Object.defineProperty(target, name, { get: all[name] })
+      // for (var p in m) exports[p] = m[p]
+      Stmt::ForIn(ForInStmt { left, right, body, .. }) => {
+        let (target, getters) = self.get_key_copy_target(&[body.as_ref().clone()], get_for_head_name(left)?)?;
+        let from = param_index(right)?;
+        if let Some(getters) = getters {
+          if param_index(&getters) != Some(from) {
+            return None;
+          }
+          return Some(ExportHelper::DefineGetters {
+            target: param_index(&target)?,
+            getters: from,
+          });
+        }
+        self.as_copy_helper(from, &target, param_index)
+      }
+      // for (let key of Object.keys(from)) Object.defineProperty(to, key, { get: () => from[key] })
+      Stmt::ForOf(ForOfStmt { left, right, body, .. }) => {
+        let Expr::Call(keys_call) = right.as_ref() else {
+          return None;
+        };
+        if !self.is_builtin_call(keys_call, "Object.keys")
+          && !self.is_builtin_call(keys_call, "Object.getOwnPropertyNames")
+        {
+          return None;
+        }
+        let from = param_index(keys_call.args.first()?.expr.as_ref())?;
+        let (target, _) = self.get_key_copy_target(&[body.as_ref().clone()], get_for_head_name(left)?)?;
+        self.as_copy_helper(from, &target, param_index)
+      }
+      _ => None,
+    }
+  }
+
+  fn as_copy_helper(
+    &self,
+    from: usize,
+    to: &Expr,
+    param_index: impl Fn(&Expr) -> Option<usize>,
+  ) -> Option<ExportHelper> {
+    if let Some(to) = param_index(to) {
+      return Some(ExportHelper::ExportStar { from, to });
+    } else if self.is_exports_expr(to) {
+      return Some(ExportHelper::CopyToExports { from });
+    }
+    None
+  }
+
+  // Object.keys(foo).forEach(function (k) { Object.defineProperty(exports, k, { get: function () { return foo[k] } }) })
+  // Object.keys(foo).forEach(function (k) { exports[k] = foo[k] })
+  // returns the source object, the copy target and the object the getters are read from
+  fn get_keys_copy<'a>(&self, call: &'a CallExpr) -> Option<(&'a Expr, Expr, Option<Expr>)> {
+    let Some(Expr::Member(MemberExpr { obj, prop, .. })) = with_expr_callee(call) else {
+      return None;
+    };
+    let Expr::Call(keys_call) = obj.as_ref() else {
+      return None;
+    };
+    if get_prop_name(prop).as_deref() != Some("forEach") || !self.is_builtin_call(keys_call, "Object.keys") {
+      return None;
+    }
+    let from = keys_call.args.first()?.expr.as_ref();
+    let desc = self.as_function(call.args.first()?.expr.as_ref())?;
+    let Some(Pat::Ident(BindingIdent { id: key, .. })) = desc.params.first() else {
+      return None;
+    };
+    let (target, getters) = self.get_key_copy_target(&desc.stmts, key.sym.as_ref())?;
+    Some((from, target, getters))
+  }
+
+  // if (key !== "default") Object.defineProperty(target, key, { enumerable: true, get: all[key] })
+  // key !== "default" && (target[key] = source[key])
+  fn get_key_copy_target(&self, stmts: &[Stmt], key: &str) -> Option<(Expr, Option<Expr>)> {
+    for stmt in stmts {
+      let found = match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => self.get_key_copy_target_from_expr(expr, key),
+        Stmt::Block(BlockStmt { stmts, .. }) => self.get_key_copy_target(stmts, key),
+        Stmt::If(IfStmt { cons, alt, .. }) => self
+          .get_key_copy_target(&[cons.as_ref().clone()], key)
+          .or_else(|| self.get_key_copy_target(&[alt.as_ref()?.as_ref().clone()], key)),
+        _ => None,
+      };
+      if found.is_some() {
+        return found;
+      }
+    }
+    None
+  }
+
+  fn get_key_copy_target_from_expr(&self, expr: &Expr, key: &str) -> Option<(Expr, Option<Expr>)> {
+    let is_key = |expr: &Expr| matches!(expr, Expr::Ident(id) if id.sym.as_ref().eq(key));
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.get_key_copy_target_from_expr(expr, key),
+      Expr::Seq(SeqExpr { exprs, .. }) => exprs
+        .iter()
+        .find_map(|expr| self.get_key_copy_target_from_expr(expr, key)),
+      Expr::Bin(BinExpr { left, right, .. }) => self
+        .get_key_copy_target_from_expr(right, key)
+        .or_else(|| self.get_key_copy_target_from_expr(left, key)),
+      Expr::Cond(CondExpr { cons, alt, .. }) => self
+        .get_key_copy_target_from_expr(cons, key)
+        .or_else(|| self.get_key_copy_target_from_expr(alt, key)),
+      Expr::Call(call) => {
+        if self.is_builtin_call(call, "Object.defineProperty") && call.args.len() >= 3 && is_key(&call.args[1].expr) {
+          let mut getters = None;
+          if let Expr::Object(ObjectLit { props, .. }) = call.args[2].expr.as_ref() {
+            for prop in props {
+              if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(KeyValueProp { key: name, value }) = prop.as_ref() {
+                  if let Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Computed(ComputedPropName { expr: prop, .. }),
+                    ..
+                  }) = value.as_ref()
+                  {
+                    if stringify_prop_name(name).as_deref() == Some("get") && is_key(prop) {
+                      getters = Some(obj.as_ref().clone());
+                    }
+                  }
+                }
+              }
+            }
+          }
+          return Some((call.args[0].expr.as_ref().clone(), getters));
+        }
+        // __createBinding(exports, m, p)
+        if call.args.len() >= 3
+          && call.args[1..].iter().any(|arg| is_key(&arg.expr))
+          && self
+            .as_function(with_expr_callee(call)?)
+            .is_some_and(|desc| self.is_binding_helper(&desc))
+        {
+          return Some((call.args[0].expr.as_ref().clone(), None));
+        }
+        None
+      }
+      Expr::Assign(AssignExpr {
+        op: AssignOp::Assign,
+        left,
+        ..
+      }) => {
+        let MemberExpr {
+          obj,
+          prop: MemberProp::Computed(ComputedPropName { expr: prop, .. }),
+          ..
+        } = get_member_expr_from_assign_target(left)?
+        else {
+          return None;
+        };
+        if is_key(prop) {
+          return Some((obj.as_ref().clone(), None));
+        }
+        None
+      }
+      _ => None,
+    }
+  }
+
+  // function __createBinding(o, m, k, k2) { ...; Object.defineProperty(o, k2, desc) }
+  // function __createBinding(o, m, k, k2) { ...; o[k2] = m[k] }
+  fn is_binding_helper(&self, desc: &FnDesc) -> bool {
+    let Some(Pat::Ident(BindingIdent { id: target, .. })) = desc.params.first() else {
+      return false;
+    };
+    desc.stmts.iter().any(|stmt| match stmt {
+      Stmt::Expr(ExprStmt { expr, .. }) => self.is_binding_expr(expr, target.sym.as_ref()),
+      _ => false,
+    })
+  }
+
+  fn is_binding_expr(&self, expr: &Expr, target: &str) -> bool {
+    let is_target = |expr: &Expr| matches!(expr, Expr::Ident(id) if id.sym.as_ref().eq(target));
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => self.is_binding_expr(expr, target),
+      Expr::Seq(SeqExpr { exprs, .. }) => exprs.iter().any(|expr| self.is_binding_expr(expr, target)),
+      Expr::Bin(BinExpr { left, right, .. }) => {
+        self.is_binding_expr(left, target) || self.is_binding_expr(right, target)
+      }
+      Expr::Cond(CondExpr { cons, alt, .. }) => self.is_binding_expr(cons, target) || self.is_binding_expr(alt, target),
+      Expr::Call(call) => {
+        self.is_builtin_call(call, "Object.defineProperty") && call.args.first().is_some_and(|arg| is_target(&arg.expr))
+      }
+      Expr::Assign(AssignExpr {
+        op: AssignOp::Assign,
+        left,
+        ..
+      }) => matches!(
+        get_member_expr_from_assign_target(left),
+        Some(MemberExpr {
+          obj,
+          prop: MemberProp::Computed(_),
+          ..
+        }) if is_target(obj)
+      ),
+      _ => false,
+    }
+  }
+
+  fn use_export_helper(&mut self, helper: ExportHelper, args: &[ExprOrSpread]) {
+    let arg = |index: usize| {
+      args
+        .get(index)
+        .filter(|arg| arg.spread.is_none())
+        .map(|arg| arg.expr.as_ref())
+    };
+    match helper {
+      ExportHelper::DefineGetter { target, name } => {
+        if let (Some(target), Some(name)) = (arg(target), arg(name)) {
+          if self.is_exports_expr(target) {
+            if let Some(name) = self.as_str(name) {
+              self.exports.insert(name);
+            }
+          }
+        }
+      }
+      ExportHelper::DefineGetters { target, getters } => {
+        if let (Some(target), Some(getters)) = (arg(target), arg(getters)) {
+          if self.is_exports_expr(target) {
+            if let Some(props) = self.as_obj(getters) {
+              self.use_object_as_exports(props);
+            }
+          }
+        }
+      }
+      ExportHelper::ExportStar { from, to } => {
+        if let (Some(from), Some(to)) = (arg(from), arg(to)) {
+          self.use_export_star(from, to);
+        }
+      }
+      ExportHelper::InteropFlag { target } => {
+        if let Some(target) = arg(target) {
+          if self.is_exports_expr(target) {
+            self.exports.insert("__esModule".to_owned());
+          }
+        }
+      }
+      ExportHelper::CopyToExports { from } => {
+        if let Some(from) = arg(from) {
+          if let Some(props) = self.as_obj(from) {
+            self.use_object_as_exports(props);
+          } else if let Some(reexport) = self.as_reexport(from) {
+            self.reexports.insert(Reexport::namespace(reexport));
+          }
+        }
+      }
+    }
+  }
+
+  // copy all the exports of `from` to `to` if `to` is the exports object
+  fn use_export_star(&mut self, from: &Expr, to: &Expr) {
+    if self.is_exports_expr(to) {
+      if let Some(props) = self.as_obj(from) {
+        self.use_object_as_exports(props);
+      } else if let Some(reexport) = self.as_reexport(from) {
+        self.reexports.insert(Reexport::namespace(reexport));
       }
     }
   }
@@ -363,6 +1669,10 @@ impl CJSLexer {
   fn is_true(&self, expr: &Expr) -> bool {
     match expr {
       Expr::Paren(ParenExpr { expr, .. }) => return self.is_true(expr),
+      // if (!(this instanceof Foo)) return new Foo()
+      Expr::Unary(UnaryExpr {
+        op: UnaryOp::Bang, arg, ..
+      }) if is_this_instanceof(arg) => return !self.constructing,
       Expr::Ident(id) => {
         if let Some(value) = self.idents.get(id.sym.as_ref()) {
           match value {
@@ -404,14 +1714,16 @@ impl CJSLexer {
 
   // var foo = module.exports = {};
   // foo === module.exports;
+  // var bar = exports;
+  // bar === exports;
   fn try_to_mark_exports_alias(&mut self, decl: &VarDeclarator) {
     if let Pat::Ident(id) = &decl.name {
       if let Some(init) = &decl.init {
-        if is_member(init, "module", "exports") {
+        if self.is_exports_expr(init) {
           self.exports_alias.insert(id.id.sym.as_ref().to_owned());
         } else if let Expr::Assign(assign) = init.as_ref() {
           if let Some(member) = get_member_expr_from_assign_target(&assign.left) {
-            if is_member(&Expr::Member(member.clone()), "module", "exports") {
+            if self.is_module_exports_expr(&Expr::Member(member.clone())) {
               self.exports_alias.insert(id.id.sym.as_ref().to_owned());
             }
           }
@@ -421,7 +1733,7 @@ impl CJSLexer {
   }
 
   fn is_exports_ident(&self, id: &str) -> bool {
-    return id.eq("exports") || self.exports_alias.contains(id);
+    self.exports_alias.contains(id) || (id.eq("exports") && !self.idents.contains_key(id))
   }
 
   fn is_exports_expr(&self, expr: &Expr) -> bool {
@@ -430,7 +1742,9 @@ impl CJSLexer {
         let id = id.sym.as_ref();
         self.is_exports_ident(id)
       }
-      Expr::Member(_) => is_member(expr, "module", "exports"),
+      Expr::Member(_) => self.is_module_exports_expr(expr),
+      // `this` is an alias of `exports` unless rebound by a function
+      Expr::This(_) => self.is_exports_ident("this"),
       _ => false,
     }
   }
@@ -446,6 +1760,18 @@ impl CJSLexer {
     None
   }
 
+  // exports.foo
+  // exports['foo']
+  // exports[KEY]
+  fn as_member_prop_name(&self, prop: &MemberProp) -> Option<String> {
+    if let MemberProp::Computed(ComputedPropName { expr, .. }) = prop {
+      if let Some(name) = self.as_str(expr) {
+        return Some(name);
+      }
+    }
+    get_prop_name(prop)
+  }
+
   // exports.foo || (exports.foo = {})
   // foo = exports.foo || (exports.foo = {})
   fn get_bare_export_names(&mut self, expr: &Expr) -> Option<String> {
@@ -492,26 +1818,32 @@ impl CJSLexer {
         None
       };
       if let Some(MemberExpr { obj, prop, .. }) = member {
-        let prop = get_prop_name(&prop);
+        let prop = self.as_member_prop_name(prop);
         if let Some(prop) = prop {
           match obj.as_ref() {
             Expr::Ident(obj) => {
               let obj_name = obj.sym.as_ref();
               if self.is_exports_ident(obj_name) {
                 // exports.foo = 'bar'
-                self.exports.insert(prop);
+                self.use_export(prop, Some(assign.right.as_ref()));
                 if let Expr::Assign(dep_assign) = assign.right.as_ref() {
                   self.get_exports_from_assign(dep_assign);
                 }
-              } else if obj_name.eq("module") && self.is_exports_ident(&prop) {
+              } else if self.is_module_expr(&Expr::Ident(obj.clone())) && prop.eq("exports") {
                 // module.exports = ??
                 let right_expr = assign.right.as_ref();
                 self.reset(right_expr)
               }
             }
-            Expr::Member(_) => {
-              if is_member(obj, "module", "exports") {
-                self.exports.insert(prop);
+            Expr::Member(_) => {
+              if self.is_module_exports_expr(obj) {
+                self.use_export(prop, Some(assign.right.as_ref()));
+              }
+            }
+            Expr::This(_) => {
+              // this.foo = 'bar'
+              if self.is_exports_expr(obj) {
+                self.use_export(prop, Some(assign.right.as_ref()));
               }
             }
             _ => {}
@@ -521,10 +1853,56 @@ impl CJSLexer {
         if let Some(bare_export_name) = self.get_bare_export_names(assign.right.as_ref()) {
           self.exports.insert(bare_export_name);
         }
+        // exports = module.exports = {}
+        // exports = {}
+        if let AssignTarget::Simple(SimpleAssignTarget::Ident(BindingIdent { id, .. })) = &assign.left {
+          let name = id.sym.as_ref();
+          if self.is_exports_ident(name) {
+            if self.is_exports_expr(&assign.right) {
+              return;
+            }
+            if let Expr::Assign(right) = assign.right.as_ref() {
+              if let Some(member) = get_member_expr_from_assign_target(&right.left) {
+                if self.is_module_exports_expr(&Expr::Member(member.clone())) {
+                  self.get_exports_from_assign(right);
+                  self.use_exports_alias(name.to_owned());
+                  return;
+                }
+              }
+            }
+            self.detach_exports_alias(name);
+          }
+        }
+      }
+    }
+  }
+
+  // delete exports.foo
+  fn delete_export(&mut self, expr: &Expr) {
+    if let Expr::Member(MemberExpr { obj, prop, .. }) = unwrap_paren(expr) {
+      if self.is_exports_expr(obj) {
+        if let Some(name) = self.as_member_prop_name(prop) {
+          self.exports.shift_remove(&name);
+          self.sources.shift_remove(&name);
+          self.kinds.shift_remove(&name);
+        }
       }
     }
   }
 
+  // `exports` is an alias of `module.exports` again
+  fn use_exports_alias(&mut self, name: String) {
+    self.exports_alias.insert(name);
+  }
+
+  // writes to a detached alias don't reach `module.exports` anymore
+  fn detach_exports_alias(&mut self, name: &str) {
+    self.exports_alias.shift_remove(name);
+    if name.eq("exports") {
+      self.idents.insert(name.to_owned(), IdentKind::Unkonwn);
+    }
+  }
+
   // function (e, t, r) {
   //   "use strict";
   //   r.r(t), r.d(t, "named", (function () { return n }));
@@ -600,54 +1978,66 @@ impl CJSLexer {
   }
 
   fn get_webpack_exports(&mut self, stmts: &Vec<Stmt>, webpack_require_sym: &str, first_stmt_index: &usize) {
-    stmts.iter().skip(*first_stmt_index).take(8).find(|stmt| match stmt {
-      Stmt::Expr(ExprStmt { expr, .. }) => {
-        if let Expr::Seq(SeqExpr { exprs, .. }) = &**expr {
-          let mut found_webpack_require_exprs = false;
-          for expr in exprs {
-            if let Expr::Call(call) = &**expr {
-              if let Some(Expr::Member(MemberExpr { obj, prop, .. })) = with_expr_callee(call) {
-                if let (Expr::Ident(Ident { sym: obj_sym, .. }), MemberProp::Ident(Ident { sym: prop_sym, .. })) =
-                  (&**obj, &*prop)
-                {
-                  if !obj_sym.as_ref().eq(webpack_require_sym) {
-                    return false;
-                  }
-                  let prop_sym_ref = prop_sym.as_ref();
+    // only the object returned by the bundle is its exports
+    let webpack_exports_sym = get_returned_ident(stmts);
+    for stmt in stmts.iter().skip(*first_stmt_index) {
+      match stmt {
+        Stmt::Expr(ExprStmt { expr, .. }) => {
+          if self.get_webpack_exports_from_expr(expr, webpack_require_sym, webpack_exports_sym) {
+            return;
+          }
+        }
+        // return e.r(t), e.d(t, { foo: () => n }), t
+        Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => {
+          if webpack_exports_sym.is_some() {
+            self.get_webpack_exports_from_expr(arg, webpack_require_sym, webpack_exports_sym);
+          }
+          return;
+        }
+        Stmt::Return(_) => return,
+        _ => {}
+      }
+    }
+  }
 
-                  if prop_sym_ref.eq("r") {
-                    self.exports.insert("__esModule".to_string());
-                    found_webpack_require_exprs = true;
-                  }
-                  if prop_sym_ref.eq("d") {
-                    let CallExpr { args, .. } = &*call;
-                    if let Some(ExprOrSpread { expr, .. }) = args.get(1) {
-                      if let Expr::Object(ObjectLit { props, .. }) = &**expr {
-                        for prop in props {
-                          if let PropOrSpread::Prop(prop) = prop {
-                            if let Prop::KeyValue(KeyValueProp {
-                              key: PropName::Ident(Ident { sym, .. }),
-                              ..
-                            }) = &**prop
-                            {
-                              self.exports.insert(sym.as_ref().to_string());
-                              found_webpack_require_exprs = true;
-                            }
-                          }
-                        }
-                      }
-                    }
-                  }
-                }
-              }
-            }
+  // e.r(t), e.d(t, { foo: () => n })
+  // (() => { e.r(t); e.d(t, { "my-name": () => n }) })()
+  fn get_webpack_exports_from_expr(
+    &mut self,
+    expr: &Expr,
+    webpack_require_sym: &str,
+    webpack_exports_sym: Option<&str>,
+  ) -> bool {
+    match expr {
+      Expr::Seq(SeqExpr { exprs, .. }) => {
+        let mut found_webpack_require_exprs = false;
+        for expr in exprs {
+          if self.get_webpack_exports_from_expr(expr, webpack_require_sym, webpack_exports_sym) {
+            found_webpack_require_exprs = true;
+          }
+        }
+        found_webpack_require_exprs
+      }
+      Expr::Call(call) => {
+        if let Some((webpack_require, target, names)) = get_webpack_define_call(call) {
+          let is_exports = match (webpack_exports_sym, target) {
+            (Some(sym), Expr::Ident(id)) => id.sym.as_ref().eq(sym),
+            (Some(_), _) => false,
+            (None, _) => true,
+          };
+          if webpack_require.eq(webpack_require_sym) && is_exports {
+            self.exports.extend(names);
+            return true;
           }
-          return found_webpack_require_exprs;
+        } else if let Some(body) = is_runtime_iife_call(call) {
+          let exports_len = self.exports.len();
+          self.get_webpack_exports(&body, webpack_require_sym, &0);
+          return self.exports.len() > exports_len;
         }
-        return false;
+        false
       }
       _ => false,
-    });
+    }
   }
 
   fn get_webpack_require_props_from_props(&mut self, props: &Vec<PropOrSpread>) -> i32 {
@@ -747,62 +2137,178 @@ impl CJSLexer {
       })
       .sum();
   }
-  fn is_umd_iife_call(&mut self, call: &CallExpr) -> Option<Vec<Stmt>> {
-    if call.args.len() == 2 {
-      let mut arg1 = call.args.get(1).unwrap().expr.as_ref();
-      if let Expr::Paren(ParenExpr { expr, .. }) = arg1 {
-        arg1 = expr.as_ref();
-      }
-      let stmts = match arg1 {
-        Expr::Fn(func) => {
-          if let Some(BlockStmt { stmts, .. }) = &func.function.body {
-            Some(stmts.clone())
-          } else {
-            None
+
+  // (function() { ... })()
+  // (function(root) { ... })(this)
+  // (function() { ... }).call(this)
+  // (function(global, factory) { ... })(this, function(exports) { ... })
+  // function init() { exports.foo = 'bar' }; init()
+  // function setup(e) { e.foo = 'bar' }; setup(module.exports)
+  fn walk_call(&mut self, call: &CallExpr) {
+    if let Some((desc, this_arg, args)) = get_iife_call(call) {
+      for arg in args {
+        if arg.spread.is_none() {
+          // (function() { ... })(exports.foo || (exports.foo = {}))
+          if let Some(bare_export_name) = self.get_bare_export_names(arg.expr.as_ref()) {
+            self.exports.insert(bare_export_name);
           }
         }
-        Expr::Arrow(arrow) => Some(get_arrow_body_as_stmts(&arrow)),
-        _ => None,
+      }
+      self.walk_fn_body(desc, this_arg, args, false);
+    } else if let Some(Expr::Ident(callee)) = with_expr_callee(call) {
+      if let Some(desc) = self.as_function(&Expr::Ident(callee.clone())) {
+        self.walk_fn_call(desc, &call.args, false);
+      }
+    }
+  }
+
+  // bind `this` and the call arguments to the params of the function walked by `lexer`
+  fn bind_fn_params(&self, lexer: &mut CJSLexer, desc: &FnDesc, this_arg: Option<&Expr>, args: &[ExprOrSpread]) {
+    if !desc.is_arrow && !this_arg.is_some_and(|this_arg| self.is_exports_expr(this_arg)) {
+      lexer.exports_alias.shift_remove("this");
+    }
+    let spread_index = args.iter().position(|arg| arg.spread.is_some());
+    for (index, param) in desc.params.iter().enumerate() {
+      let (name, default) = match param {
+        Pat::Ident(BindingIdent { id, .. }) => (id.sym.as_ref(), None),
+        Pat::Assign(AssignPat { left, right, .. }) => match left.as_ref() {
+          Pat::Ident(BindingIdent { id, .. }) => (id.sym.as_ref(), Some(right.as_ref())),
+          _ => continue,
+        },
+        _ => continue,
       };
-      let expr = if let Some(callee) = with_expr_callee(call) {
-        match callee {
-          Expr::Paren(ParenExpr { expr, .. }) => expr.as_ref(),
-          _ => callee,
+      lexer.exports_alias.shift_remove(name);
+      lexer.idents.shift_remove(name);
+      if spread_index.is_some_and(|spread_index| index >= spread_index) {
+        lexer.idents.insert(name.to_owned(), IdentKind::Unkonwn);
+        continue;
+      }
+      if let Some(arg) = args.get(index).map(|arg| arg.expr.as_ref()).or(default) {
+        if self.is_exports_expr(arg) {
+          lexer.exports_alias.insert(name.to_owned());
+        } else if let Some(kind) = self.as_arg_kind(name, arg) {
+          lexer.idents.insert(name.to_owned(), kind);
         }
       } else {
-        return None;
-      };
-      match expr {
-        Expr::Fn(func) => {
-          if is_umd_params(&func.function.params.iter().map(|p| p.pat.clone()).collect()) {
-            return stmts;
-          } else if let Some(BlockStmt { stmts: body_stmts, .. }) = &func.function.body {
-            if is_umd_checks(body_stmts) {
-              if let Some(Param {
-                pat: Pat::Ident(BindingIdent {
-                  id: Ident { sym, .. }, ..
+        // a missing argument is `undefined`, and shadows globals like `require`
+        lexer
+          .idents
+          .insert(name.to_owned(), IdentKind::Alias("undefined".to_owned()));
+      }
+    }
+  }
+
+  // mark idents updated by an expression statement
+  fn mark_expr(&mut self, expr: &Expr) {
+    match expr {
+      Expr::Assign(assign) => {
+        if assign.op == AssignOp::Assign {
+          match &assign.left {
+            AssignTarget::Simple(simple) => match simple {
+              // var foo = 'boo'
+              // foo = 'bar'
+              SimpleAssignTarget::Ident(BindingIdent { id, .. }) => {
+                let id = id.sym.as_ref();
+                if self.idents.contains_key(id) {
+                  self.mark_ident(id, &assign.right.as_ref())
+                }
+              }
+              // var foo = {}
+              // foo.bar = 'bar'
+              SimpleAssignTarget::Member(MemberExpr { obj, prop, .. }) => {
+                let key = get_prop_name(&prop);
+                if let Some(key) = key {
+                  if let Expr::Ident(obj_id) = obj.as_ref() {
+                    let obj_name = obj_id.sym.as_ref();
+                    if let Some(mut props) = self.as_obj(&obj) {
+                      props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident(&key)),
+                        value: assign.right.clone(),
+                      }))));
+                      self.idents.insert(obj_name.into(), IdentKind::Object(props));
+                    } else if key.eq("prototype") {
+                      // Foo.prototype = { run() {} }
+                      let members = self
+                        .as_obj(&assign.right)
+                        .map(|props| self.get_object_kinds(props))
+                        .unwrap_or_default();
+                      self.augment_ident(obj_name, true, members, true);
+                    } else {
+                      let kind = self.as_value_kind(&assign.right);
+                      self.augment_ident(obj_name, false, IndexMap::from([(key, kind)]), false);
+                    }
+                  } else if let Some(obj_name) = get_prototype_owner(obj) {
+                    // Foo.prototype.run = function () {}
+                    let kind = self.as_value_kind(&assign.right);
+                    self.augment_ident(obj_name, true, IndexMap::from([(key, kind)]), false);
+                  }
+                }
+              }
+              _ => {}
+            },
+            _ => {}
+          };
+        }
+      }
+      // e.r(t), e.d(t, { foo: () => n })
+      Expr::Seq(SeqExpr { exprs, .. }) => {
+        for expr in exprs {
+          self.mark_expr(expr);
+        }
+      }
+      Expr::Call(call) => {
+        if let Some((Expr::Ident(target), names)) = self.get_webpack_define_call(call) {
+          let target = target.sym.as_ref();
+          if let Some(IdentKind::Object(mut props)) = self.idents.get(target).cloned() {
+            for name in names {
+              let key = PropName::Ident(quote_ident(&name));
+              // unresolved getters are kept as getters so the kind stays unknown
+              let prop = match self.get_webpack_define_value(call, &name) {
+                Some(value) => Prop::KeyValue(KeyValueProp {
+                  key,
+                  value: Box::new(value),
                 }),
-                ..
-              }) = &func.function.params.get(0)
-              {
-                self.exports_alias.insert(sym.as_ref().to_owned());
+                None => Prop::Getter(GetterProp {
+                  span: DUMMY_SP,
+                  key,
+                  type_ann: None,
+                  body: None,
+                }),
+              };
+              props.push(PropOrSpread::Prop(Box::new(prop)));
+            }
+            self.idents.insert(target.into(), IdentKind::Object(props));
+          }
+        } else if self.is_builtin_call(call, "Object.assign") && call.args.len() >= 2 {
+          // Object.assign(Foo, { create })
+          // Object.assign(Foo.prototype, { run() {} })
+          let target = call.args[0].expr.as_ref();
+          let owner = match target {
+            Expr::Ident(id) => Some((id.sym.as_ref(), false)),
+            _ => get_prototype_owner(target).map(|name| (name, true)),
+          };
+          if let Some((name, prototype)) = owner {
+            let mut members = IndexMap::new();
+            for arg in &call.args[1..] {
+              if let Some(props) = self.as_obj(&arg.expr) {
+                members.extend(self.get_object_kinds(props));
               }
-
-              return stmts;
             }
-            return None;
+            self.augment_ident(name, prototype, members, false);
           }
-        }
-        Expr::Arrow(arrow) => {
-          // TODO: detect for minified umd, haven't seen any in the wild using arrow fns yet though
-          if is_umd_params(&arrow.params) {
-            return stmts;
+        } else if let Some(body) = is_runtime_iife_call(call) {
+          // (() => { __webpack_require__.d = (exports, definition) => { ... } })()
+          for stmt in body {
+            match stmt {
+              Stmt::Expr(ExprStmt { expr, .. }) => self.mark_expr(&expr),
+              Stmt::Return(_) => break,
+              _ => {}
+            }
           }
         }
-        _ => {}
       }
+      _ => {}
     }
-    None
   }
 
   // walk and mark idents
@@ -853,6 +2359,32 @@ impl CJSLexer {
                         _ => {}
                       }
                     }
+                  } else if let Some(init) = &decl.init {
+                    // var { defineProperty, assign: extend } = Object
+                    for prop in props {
+                      let (key, rename) = match prop {
+                        ObjectPatProp::Assign(AssignPatProp { key, .. }) => {
+                          (Some(key.sym.as_ref().to_owned()), &key.id)
+                        }
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => match value.as_ref() {
+                          Pat::Ident(rename) => (stringify_prop_name(key), &rename.id),
+                          _ => continue,
+                        },
+                        _ => continue,
+                      };
+                      if let Some(key) = key {
+                        let member = Expr::Member(MemberExpr {
+                          span: DUMMY_SP,
+                          obj: init.clone(),
+                          prop: MemberProp::Ident(quote_ident(&key)),
+                        });
+                        if let Some(builtin) = self.as_builtin(&member) {
+                          self
+                            .idents
+                            .insert(rename.sym.as_ref().to_owned(), IdentKind::Builtin(builtin));
+                        }
+                      }
+                    }
                   }
                 }
                 _ => {}
@@ -879,56 +2411,23 @@ impl CJSLexer {
           }
           _ => {}
         },
-        Stmt::Expr(ExprStmt { expr, .. }) => {
-          match expr.as_ref() {
-            Expr::Assign(assign) => {
-              if assign.op == AssignOp::Assign {
-                match &assign.left {
-                  AssignTarget::Simple(simple) => match simple {
-                    // var foo = 'boo'
-                    // foo = 'bar'
-                    SimpleAssignTarget::Ident(BindingIdent { id, .. }) => {
-                      let id = id.sym.as_ref();
-                      if self.idents.contains_key(id) {
-                        self.mark_ident(id, &assign.right.as_ref())
-                      }
-                    }
-                    // var foo = {}
-                    // foo.bar = 'bar'
-                    SimpleAssignTarget::Member(MemberExpr { obj, prop, .. }) => {
-                      let key = get_prop_name(&prop);
-                      if let Some(key) = key {
-                        if let Expr::Ident(obj_id) = obj.as_ref() {
-                          let obj_name = obj_id.sym.as_ref();
-                          if let Some(mut props) = self.as_obj(&obj) {
-                            props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                              key: PropName::Ident(quote_ident(&key)),
-                              value: Box::new(Expr::Lit(Lit::Bool(Bool {
-                                span: DUMMY_SP,
-                                value: true,
-                              }))),
-                            }))));
-                            self.idents.insert(obj_name.into(), IdentKind::Object(props));
-                          } else if let Some(FnDesc { stmts, mut extends }) = self.as_function(&obj) {
-                            extends.push(key.to_owned());
-                            self
-                              .idents
-                              .insert(obj_name.into(), IdentKind::Fn(FnDesc { stmts, extends }));
-                          }
-                        }
-                      }
-                    }
-                    _ => {}
-                  },
-                  _ => {}
-                };
-              }
-            }
-            _ => {}
-          };
-        }
+        Stmt::Expr(ExprStmt { expr, .. }) => self.mark_expr(expr),
         Stmt::Block(BlockStmt { stmts, .. }) => {
+          // `let` and `const` declared in the block don't shadow the outer bindings after it
+          let scoped = get_block_scoped_names(stmts)
+            .into_iter()
+            .map(|name| {
+              let kind = self.idents.get(&name).cloned();
+              (name, kind)
+            })
+            .collect::<Vec<_>>();
           let returned = self.walk_stmts(&stmts);
+          for (name, kind) in scoped {
+            match kind {
+              Some(kind) => self.idents.insert(name, kind),
+              None => self.idents.shift_remove(&name),
+            };
+          }
           if returned {
             return true;
           }
@@ -970,62 +2469,49 @@ impl CJSLexer {
       // Object.defineProperty(exports, 'foo', { value: 'bar' })
       // Object.defineProperty(module.exports, 'foo', { value: 'bar' })
       // Object.defineProperty(module, 'exports', { value: { foo: 'bar' }})
+      // Object.defineProperties(exports, { foo: { value: 'bar' } })
+      // Reflect.defineProperty(exports, 'foo', { value: 'bar' })
+      // exports.__defineGetter__('foo', function() { return 'bar' })
+      // __webpack_require__.d(exports, { foo: () => bar })
       // Object.assign(exports, { foo: 'bar' })
       // Object.assign(module.exports, { foo: 'bar' }, { ...require('a') }, require('b'))
       // Object.assign(module, { exports: { foo: 'bar' } })
       // Object.assign(module, { exports: require('lib') })
       // (function() { ... })()
+      // (function() { ... }).call(this)
       // require("tslib").__exportStar(..., exports)
       // tslib.__exportStar(..., exports)
       // __exportStar(..., exports)
+      // $parcel$export(module.exports, "foo", () => foo)
+      // _export(exports, { foo: function() { return foo } })
+      // _export_star(require("./foo"), exports)
+      // Object.keys(foo).forEach(function (k) { exports[k] = foo[k] })
+      // define(['exports'], function (exports) { ... })
       Expr::Call(call) => {
-        if is_object_static_mothod_call(&call, "defineProperty") && call.args.len() >= 3 {
-          let arg0 = &call.args[0];
-          let arg1 = &call.args[1];
-          let arg2 = &call.args[2];
-          let is_module = is_module_ident(arg0.expr.as_ref());
-          let is_exports = self.is_exports_expr(arg0.expr.as_ref());
-
-          let name = self.as_str(arg1.expr.as_ref());
-          let mut with_value_or_getter = false;
-          let mut with_value: Option<Expr> = None;
-          if let Some(props) = self.as_obj(arg2.expr.as_ref()) {
+        if (self.is_builtin_call(call, "Object.defineProperty") || self.is_builtin_call(call, "Reflect.defineProperty"))
+          && call.args.len() >= 3
+        {
+          let name = self.as_str(call.args[1].expr.as_ref());
+          self.define_property(call.args[0].expr.as_ref(), name, call.args[2].expr.as_ref());
+        } else if self.is_builtin_call(call, "Object.defineProperties") && call.args.len() >= 2 {
+          if let Some(props) = self.as_obj(call.args[1].expr.as_ref()) {
             for prop in props {
               if let PropOrSpread::Prop(prop) = prop {
-                let key = match prop.as_ref() {
-                  Prop::KeyValue(KeyValueProp { key, value, .. }) => {
-                    let key = stringify_prop_name(key);
-                    if let Some(key) = &key {
-                      if key.eq("value") {
-                        with_value = Some(value.as_ref().clone());
-                      }
-                    }
-                    key
-                  }
-                  Prop::Method(MethodProp { key, .. }) => stringify_prop_name(key),
-                  _ => None,
-                };
-                if let Some(key) = key {
-                  if key.eq("value") || key.eq("get") {
-                    with_value_or_getter = true;
-                    break;
-                  }
+                if let Prop::KeyValue(KeyValueProp { key, value }) = prop.as_ref() {
+                  let name = self.as_prop_name(key);
+                  self.define_property(call.args[0].expr.as_ref(), name, value);
                 }
               }
             }
           }
-          if is_exports && with_value_or_getter {
-            if let Some(name) = name {
-              self.exports.insert(name);
-            }
-          }
-          if is_module {
-            if let Some(expr) = with_value {
-              self.reset(&expr);
-            }
+        } else if let Some(name) = self.is_exports_define_getter_call(call) {
+          self.exports.insert(name);
+        } else if let Some((target, names)) = self.get_webpack_define_call(call) {
+          if self.is_exports_expr(target) {
+            self.exports.extend(names);
           }
-        } else if is_object_static_mothod_call(&call, "assign") && call.args.len() >= 2 {
-          let is_module = is_module_ident(call.args[0].expr.as_ref());
+        } else if self.is_builtin_call(call, "Object.assign") && call.args.len() >= 2 {
+          let is_module = self.is_module_expr(call.args[0].expr.as_ref());
           let is_exports = self.is_exports_expr(call.args[0].expr.as_ref());
           for arg in &call.args[1..] {
             if let Some(props) = self.as_obj(arg.expr.as_ref()) {
@@ -1036,7 +2522,7 @@ impl CJSLexer {
                     if let Prop::KeyValue(KeyValueProp { key, value, .. }) = prop.as_ref() {
                       let key = stringify_prop_name(key);
                       if let Some(key) = &key {
-                        if self.is_exports_ident(key) {
+                        if key.eq("exports") {
                           with_exports = Some(value.as_ref().clone());
                           break;
                         }
@@ -1052,57 +2538,50 @@ impl CJSLexer {
               }
             } else if let Some(reexport) = self.as_reexport(arg.expr.as_ref()) {
               if is_exports {
-                self.reexports.insert(reexport);
+                self.reexports.insert(Reexport::namespace(reexport));
               }
             }
           }
+        } else if self.is_amd_define_call(call) {
+          self.walk_amd_define(&call.args);
         } else if is_tslib_export_star_call(&call) && call.args.len() >= 2 {
-          let is_exports = self.is_exports_expr(call.args[1].expr.as_ref());
-          if is_exports {
-            if let Some(props) = self.as_obj(call.args[0].expr.as_ref()) {
-              self.use_object_as_exports(props);
-            } else if let Some(reexport) = self.as_reexport(call.args[0].expr.as_ref()) {
-              self.reexports.insert(reexport);
+          self.use_export_star(call.args[0].expr.as_ref(), call.args[1].expr.as_ref());
+        } else if let Some(helper) = with_expr_callee(call).and_then(|callee| self.as_export_helper(callee)) {
+          self.use_export_helper(helper, &call.args);
+        } else if let Some((from, to, _)) = self.get_keys_copy(call) {
+          self.use_export_star(from, &to);
+        } else if let Some((values, desc)) = self.get_for_each_call(call) {
+          if self.use_loop_budget(values.len()) {
+            for (index, value) in values.into_iter().enumerate() {
+              let args = [value, Expr::Lit(Lit::Num(Number::from(index as f64)))].map(|expr| ExprOrSpread {
+                spread: None,
+                expr: Box::new(expr),
+              });
+              self.walk_fn_body(desc.clone(), None, &args, false);
             }
           }
         } else if is_export_call(&call) && call.args.len() > 0 {
           if let Some(props) = self.as_obj(call.args[0].expr.as_ref()) {
             self.use_object_as_exports(props);
           } else if let Some(reexport) = self.as_reexport(call.args[0].expr.as_ref()) {
-            self.reexports.insert(reexport);
-          }
-        } else if let Some(body) = self.is_umd_iife_call(&call) {
-          self.walk_body(body, false);
-        } else if let Some(body) = is_iife_call(&call) {
-          for arg in &call.args {
-            if arg.spread.is_none() {
-              // (function() { ... })(exports.foo || (exports.foo = {}))
-              if let Some(bare_export_name) = self.get_bare_export_names(arg.expr.as_ref()) {
-                self.exports.insert(bare_export_name);
-              }
-            }
+            self.reexports.insert(Reexport::namespace(reexport));
           }
-          self.walk_body(body, false);
+        } else {
+          self.walk_call(call);
         }
       }
       // ~function(){ ... }()
       // !(function(e, t) { ... })(this, (function (e) { ... }));
+      // delete exports.foo
+      Expr::Unary(UnaryExpr {
+        op: UnaryOp::Delete,
+        arg,
+        ..
+      }) => self.delete_export(arg),
       Expr::Unary(UnaryExpr { op, arg, .. }) => {
         if let UnaryOp::Minus | UnaryOp::Plus | UnaryOp::Bang | UnaryOp::Tilde | UnaryOp::Void = op {
           if let Expr::Call(call) = arg.as_ref() {
-            if let Some(body) = self.is_umd_iife_call(&call) {
-              self.walk_body(body, false);
-            } else if let Some(body) = is_iife_call(&call) {
-              // (function() { ... })(exports.foo || (exports.foo = {}))
-              for arg in &call.args {
-                if arg.spread.is_none() {
-                  if let Some(bare_export_name) = self.get_bare_export_names(arg.expr.as_ref()) {
-                    self.exports.insert(bare_export_name);
-                  }
-                }
-              }
-              self.walk_body(body, false);
-            }
+            self.walk_call(call);
           }
         }
       }
@@ -1116,6 +2595,14 @@ impl CJSLexer {
           false,
         );
       }
+      // typeof exports === 'object' ? module.exports = factory() : root.foo = factory()
+      Expr::Cond(CondExpr { test, cons, alt, .. }) => {
+        if self.is_true(test) {
+          self.parse_expr(cons);
+        } else {
+          self.parse_expr(alt);
+        }
+      }
       // 0 && (module.exports = { foo })
       Expr::Bin(BinExpr { op, right, .. }) => {
         if matches!(op, BinaryOp::LogicalAnd) {
@@ -1155,7 +2642,10 @@ impl CJSLexer {
           Stmt::Return(ReturnStmt { arg, .. }) => {
             self.fn_returned = true;
             if let Some(arg) = arg {
-              self.reset(arg);
+              // function Foo() { this.a = 1; return 1 } => new Foo() is still the instance
+              if !self.constructing || self.as_value_kind(arg) != ValueKind::Primitive {
+                self.reset(arg);
+              }
             }
           }
           _ => {}
@@ -1191,85 +2681,107 @@ impl CJSLexer {
         }
         Stmt::Return(ReturnStmt { arg, .. }) => {
           if let Some(arg) = arg {
-            match &**arg {
-              Expr::Call(call) => match with_expr_callee(call) {
-                Some(Expr::Fn(FnExpr { function, .. })) => {
-                  if let Function {
-                    body: Some(BlockStmt { stmts, .. }),
-                    ..
-                  } = function.as_ref()
-                  {
-                    let mut check_function = |function: &Box<Function>| {
-                      if let Function {
-                        body: Some(BlockStmt { stmts, .. }),
-                        ..
-                      } = function.as_ref()
-                      {
-                        if let Some(Stmt::If(IfStmt { cons, .. })) = stmts.get(0) {
-                          if let Stmt::Return(ReturnStmt { arg: Some(arg), .. }) = &**cons {
-                            if let Expr::Member(MemberExpr {
-                              prop: MemberProp::Ident(prop),
-                              ..
-                            }) = &**arg
-                            {
-                              if prop.sym.as_ref().eq("exports") {
-                                if call.args.len() != 1 {
-                                  return;
-                                }
-                                if let Some(ExprOrSpread { expr, .. }) = call.args.get(0) {
-                                  if let Expr::Array(ArrayLit { elems, .. }) = &**expr {
-                                    for elem in elems {
-                                      if let Some(ExprOrSpread { expr, .. }) = elem {
-                                        if let Expr::Fn(FnExpr { function, .. }) = &**expr {
-                                          if let Function {
-                                            body: Some(BlockStmt { stmts, .. }),
-                                            params,
-                                            ..
-                                          } = function.as_ref()
-                                          {
-                                            if let Some(Param {
-                                              pat:
-                                                Pat::Ident(BindingIdent {
-                                                  id:
-                                                    Ident {
-                                                      sym: webpack_exports_sym,
-                                                      ..
-                                                    },
-                                                  ..
-                                                }),
+            self.get_webpack_bundle_exports(arg);
+          }
+        }
+        // for (const method of ['get', 'post']) exports[method] = ...
+        Stmt::ForOf(ForOfStmt { left, right, body, .. }) => {
+          if let (Some(name), Some(values)) = (get_for_head_name(left), self.as_array(right)) {
+            self.walk_loop_body(name, values, body);
+          }
+        }
+        // for (var key in obj) exports[key] = obj[key]
+        Stmt::ForIn(ForInStmt { left, right, body, .. }) => {
+          if let (Some(name), Some(keys)) = (get_for_head_name(left), self.as_obj_keys(right)) {
+            let values = keys.iter().map(|key| Expr::Lit(Lit::Str(quote_str(key)))).collect();
+            self.walk_loop_body(name, values, body);
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  // webpack 4:
+  // return function (e) { ... }([function (e, t, r) { ... }])
+  // webpack 5:
+  // return (() => { "use strict"; var e = { ... }; ... return n })()
+  fn get_webpack_bundle_exports(&mut self, arg: &Expr) {
+    match arg {
+      Expr::Call(call) => match with_expr_callee(call) {
+        Some(Expr::Fn(FnExpr { function, .. })) => {
+          if let Function {
+            body: Some(BlockStmt { stmts, .. }),
+            ..
+          } = function.as_ref()
+          {
+            let mut check_function = |function: &Box<Function>| {
+              if let Function {
+                body: Some(BlockStmt { stmts, .. }),
+                ..
+              } = function.as_ref()
+              {
+                if let Some(Stmt::If(IfStmt { cons, .. })) = stmts.get(0) {
+                  if let Stmt::Return(ReturnStmt { arg: Some(arg), .. }) = &**cons {
+                    if let Expr::Member(MemberExpr {
+                      prop: MemberProp::Ident(prop),
+                      ..
+                    }) = &**arg
+                    {
+                      if prop.sym.as_ref().eq("exports") {
+                        if call.args.len() != 1 {
+                          return;
+                        }
+                        if let Some(ExprOrSpread { expr, .. }) = call.args.get(0) {
+                          if let Expr::Array(ArrayLit { elems, .. }) = &**expr {
+                            for elem in elems {
+                              if let Some(ExprOrSpread { expr, .. }) = elem {
+                                if let Expr::Fn(FnExpr { function, .. }) = &**expr {
+                                  if let Function {
+                                    body: Some(BlockStmt { stmts, .. }),
+                                    params,
+                                    ..
+                                  } = function.as_ref()
+                                  {
+                                    if let Some(Param {
+                                      pat:
+                                        Pat::Ident(BindingIdent {
+                                          id:
+                                            Ident {
+                                              sym: webpack_exports_sym,
                                               ..
-                                            }) = params.get(1)
-                                            {
-                                              if let Some(Param {
-                                                pat:
-                                                  Pat::Ident(BindingIdent {
-                                                    id:
-                                                      Ident {
-                                                        sym: webpack_require_sym,
-                                                        ..
-                                                      },
-                                                    ..
-                                                  }),
+                                            },
+                                          ..
+                                        }),
+                                      ..
+                                    }) = params.get(1)
+                                    {
+                                      if let Some(Param {
+                                        pat:
+                                          Pat::Ident(BindingIdent {
+                                            id:
+                                              Ident {
+                                                sym: webpack_require_sym,
                                                 ..
-                                              }) = params.get(2)
-                                              {
-                                                for stmt in stmts {
-                                                  if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
-                                                    self.get_webpack4_exports(
-                                                      expr,
-                                                      webpack_exports_sym.as_ref(),
-                                                      Some(webpack_require_sym.as_ref()),
-                                                    )
-                                                  }
-                                                }
-                                              } else {
-                                                for stmt in stmts {
-                                                  if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
-                                                    self.get_webpack4_exports(expr, webpack_exports_sym.as_ref(), None)
-                                                  }
-                                                }
-                                              }
-                                            }
+                                              },
+                                            ..
+                                          }),
+                                        ..
+                                      }) = params.get(2)
+                                      {
+                                        for stmt in stmts {
+                                          if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
+                                            self.get_webpack4_exports(
+                                              expr,
+                                              webpack_exports_sym.as_ref(),
+                                              Some(webpack_require_sym.as_ref()),
+                                            )
+                                          }
+                                        }
+                                      } else {
+                                        for stmt in stmts {
+                                          if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
+                                            self.get_webpack4_exports(expr, webpack_exports_sym.as_ref(), None)
                                           }
                                         }
                                       }
@@ -1281,167 +2793,165 @@ impl CJSLexer {
                           }
                         }
                       }
-                    };
-                    if let Some(Stmt::Decl(Decl::Fn(FnDecl { function, .. }))) = stmts.get(0) {
-                      check_function(function);
-                    } else if let Some(Stmt::Decl(Decl::Fn(FnDecl { function, .. }))) = stmts.get(1) {
-                      check_function(function);
                     }
                   }
                 }
-                Some(Expr::Paren(ParenExpr { expr, .. })) => {
-                  if let Expr::Arrow(ArrowExpr { body, .. }) = &**expr {
-                    if let BlockStmtOrExpr::BlockStmt(BlockStmt { stmts, .. }) = &**body {
-                      let first_stmt_index = match stmts.get(0) {
-                        Some(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
-                          Expr::Lit(Lit::Str(Str { value, .. })) => {
-                            if value.to_string().eq("use strict") {
-                              1
-                            } else {
-                              0
-                            }
-                          }
-                          _ => 0,
-                        },
-                        _ => 0,
-                      };
-
-                      if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(first_stmt_index) {
-                        let VarDecl { decls, .. } = &**var_decl;
-                        match decls.get(0) {
-                          Some(VarDeclarator {
-                            name:
-                              Pat::Ident(BindingIdent {
-                                id:
-                                  Ident {
-                                    sym: webpack_require_sym,
-                                    ..
-                                  },
-                                ..
-                              }),
-                            init,
-                            ..
-                          }) => {
-                            if let Some(init) = init {
-                              if let Expr::Object(ObjectLit { props, .. }) = &**init {
-                                let webpack_require_props = self.get_webpack_require_props_from_props(props);
-
-                                if webpack_require_props == 2 {
-                                  self.get_webpack_exports(stmts, &webpack_require_sym, &(first_stmt_index + 1));
-                                }
-                              }
-                            }
-                          }
-                          _ => {}
-                        }
-                      }
+              }
+            };
+            if let Some(Stmt::Decl(Decl::Fn(FnDecl { function, .. }))) = stmts.get(0) {
+              check_function(function);
+            } else if let Some(Stmt::Decl(Decl::Fn(FnDecl { function, .. }))) = stmts.get(1) {
+              check_function(function);
+            }
+          }
+        }
+        Some(Expr::Paren(ParenExpr { expr, .. })) => {
+          if let Expr::Arrow(ArrowExpr { body, .. }) = &**expr {
+            if let BlockStmtOrExpr::BlockStmt(BlockStmt { stmts, .. }) = &**body {
+              let first_stmt_index = match stmts.get(0) {
+                Some(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+                  Expr::Lit(Lit::Str(Str { value, .. })) => {
+                    if value.to_string().eq("use strict") {
+                      1
+                    } else {
+                      0
+                    }
+                  }
+                  _ => 0,
+                },
+                _ => 0,
+              };
 
-                      if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(first_stmt_index) {
-                        let VarDecl { decls, .. } = &**var_decl;
-                        match decls.get(0) {
-                          Some(VarDeclarator {
-                            name:
-                              Pat::Ident(BindingIdent {
-                                id:
-                                  Ident {
-                                    sym: webpack_require_sym,
-                                    ..
-                                  },
-                                ..
-                              }),
-                            init,
+              if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(first_stmt_index) {
+                let VarDecl { decls, .. } = &**var_decl;
+                match decls.get(0) {
+                  Some(VarDeclarator {
+                    name:
+                      Pat::Ident(BindingIdent {
+                        id:
+                          Ident {
+                            sym: webpack_require_sym,
                             ..
-                          }) => {
-                            if let Some(init) = init {
-                              if let Expr::Object(ObjectLit { props, .. }) = &**init {
-                                let webpack_require_props = self.get_webpack_require_props_from_props(props);
+                          },
+                        ..
+                      }),
+                    init,
+                    ..
+                  }) => {
+                    if let Some(init) = init {
+                      if let Expr::Object(ObjectLit { props, .. }) = &**init {
+                        let webpack_require_props = self.get_webpack_require_props_from_props(props);
 
-                                if webpack_require_props == 2 {
-                                  self.get_webpack_exports(stmts, &webpack_require_sym, &(first_stmt_index + 1));
-                                }
-                              }
-                            }
-                          }
-                          _ => {}
+                        if webpack_require_props == 2 {
+                          self.get_webpack_exports(stmts, &webpack_require_sym, &(first_stmt_index + 1));
                         }
                       }
+                    }
+                  }
+                  _ => {}
+                }
+              }
 
-                      if let Some(Stmt::Decl(Decl::Fn(FnDecl {
-                        ident:
+              if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(first_stmt_index) {
+                let VarDecl { decls, .. } = &**var_decl;
+                match decls.get(0) {
+                  Some(VarDeclarator {
+                    name:
+                      Pat::Ident(BindingIdent {
+                        id:
                           Ident {
                             sym: webpack_require_sym,
                             ..
                           },
                         ..
-                      }))) = stmts.get(first_stmt_index + 1)
-                      {
-                        let webpack_require_props =
-                          self.get_webpack_require_props_from_stmts(stmts, webpack_require_sym);
+                      }),
+                    init,
+                    ..
+                  }) => {
+                    if let Some(init) = init {
+                      if let Expr::Object(ObjectLit { props, .. }) = &**init {
+                        let webpack_require_props = self.get_webpack_require_props_from_props(props);
+
                         if webpack_require_props == 2 {
-                          if let Some(Stmt::Return(ReturnStmt { arg: Some(arg), .. })) = stmts.get(stmts.len() - 1) {
-                            if let Expr::Seq(SeqExpr { exprs, .. }) = &**arg {
-                              if let Some(expr) = exprs.get(0) {
-                                if let Expr::Call(call) = &**expr {
-                                  if let Some(stmts) = is_iife_call(call) {
-                                    self.get_webpack_exports(&stmts, &webpack_require_sym, &0);
-                                  }
-                                }
-                              }
-                            }
+                          self.get_webpack_exports(stmts, &webpack_require_sym, &(first_stmt_index + 1));
+                        }
+                      }
+                    }
+                  }
+                  _ => {}
+                }
+              }
+
+              if let Some(Stmt::Decl(Decl::Fn(FnDecl {
+                ident: Ident {
+                  sym: webpack_require_sym,
+                  ..
+                },
+                ..
+              }))) = stmts.get(first_stmt_index + 1)
+              {
+                let webpack_require_props = self.get_webpack_require_props_from_stmts(stmts, webpack_require_sym);
+                if webpack_require_props == 2 {
+                  if let Some(Stmt::Return(ReturnStmt { arg: Some(arg), .. })) = stmts.get(stmts.len() - 1) {
+                    if let Expr::Seq(SeqExpr { exprs, .. }) = &**arg {
+                      if let Some(expr) = exprs.get(0) {
+                        if let Expr::Call(call) = &**expr {
+                          if let Some(stmts) = is_iife_call(call) {
+                            self.get_webpack_exports(&stmts, &webpack_require_sym, &0);
                           }
                         }
                       }
+                    }
+                  }
+                }
+              }
 
-                      if let Some(Stmt::Return(ReturnStmt { arg, .. })) = stmts.get(stmts.len() - 1) {
-                        if let Some(arg) = arg {
-                          match &**arg {
-                            Expr::Seq(SeqExpr { exprs, .. }) => {
-                              if let Some(module_exports_expr) = exprs.get(exprs.len() - 1) {
-                                if let Some(module_iife_expr) = exprs.get(0) {
-                                  if let Expr::Call(module_iife_call_expr) = &**module_iife_expr {
-                                    if let Some(stmts) = is_iife_call(module_iife_call_expr) {
-                                      if let Expr::Ident(Ident {
-                                        sym: module_exports_sym,
-                                        ..
-                                      }) = &**module_exports_expr
-                                      {
-                                        if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(0) {
-                                          let VarDecl { decls, .. } = &**var_decl;
-                                          if let Some(VarDeclarator { name, init, .. }) = decls.get(0) {
-                                            if let Some(init_expr) = init {
-                                              if let Expr::Ident(Ident { sym, .. }) = &**init_expr {
-                                                if module_exports_sym.as_ref().eq(sym.as_ref()) {
-                                                  if let Pat::Ident(BindingIdent {
-                                                    id: Ident { sym, .. }, ..
-                                                  }) = name
-                                                  {
-                                                    self.exports_alias.insert(sym.as_ref().to_owned());
-                                                    self.walk_body(stmts, false);
-                                                    return;
-                                                  }
-                                                }
-                                              }
-                                            }
+              if let Some(Stmt::Return(ReturnStmt { arg, .. })) = stmts.get(stmts.len() - 1) {
+                if let Some(arg) = arg {
+                  match &**arg {
+                    Expr::Seq(SeqExpr { exprs, .. }) => {
+                      if let Some(module_exports_expr) = exprs.get(exprs.len() - 1) {
+                        if let Some(module_iife_expr) = exprs.get(0) {
+                          if let Expr::Call(module_iife_call_expr) = &**module_iife_expr {
+                            if let Some(stmts) = is_iife_call(module_iife_call_expr) {
+                              if let Expr::Ident(Ident {
+                                sym: module_exports_sym,
+                                ..
+                              }) = &**module_exports_expr
+                              {
+                                if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(0) {
+                                  let VarDecl { decls, .. } = &**var_decl;
+                                  if let Some(VarDeclarator { name, init, .. }) = decls.get(0) {
+                                    if let Some(init_expr) = init {
+                                      if let Expr::Ident(Ident { sym, .. }) = &**init_expr {
+                                        if module_exports_sym.as_ref().eq(sym.as_ref()) {
+                                          if let Pat::Ident(BindingIdent {
+                                            id: Ident { sym, .. }, ..
+                                          }) = name
+                                          {
+                                            self.exports_alias.insert(sym.as_ref().to_owned());
+                                            self.walk_body(stmts, false);
+                                            return;
                                           }
                                         }
+                                      }
+                                    }
+                                  }
+                                }
 
-                                        if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(1) {
-                                          let VarDecl { decls, .. } = &**var_decl;
-                                          if let Some(VarDeclarator { name, init, .. }) = decls.get(0) {
-                                            if let Some(init_expr) = init {
-                                              if let Expr::Ident(Ident { sym, .. }) = &**init_expr {
-                                                if module_exports_sym.as_ref().eq(sym.as_ref()) {
-                                                  if let Pat::Ident(BindingIdent {
-                                                    id: Ident { sym, .. }, ..
-                                                  }) = name
-                                                  {
-                                                    self.exports_alias.insert(sym.as_ref().to_owned());
-                                                    self.walk_body(stmts, false);
-                                                    return;
-                                                  }
-                                                }
-                                              }
-                                            }
+                                if let Some(Stmt::Decl(Decl::Var(var_decl))) = stmts.get(1) {
+                                  let VarDecl { decls, .. } = &**var_decl;
+                                  if let Some(VarDeclarator { name, init, .. }) = decls.get(0) {
+                                    if let Some(init_expr) = init {
+                                      if let Expr::Ident(Ident { sym, .. }) = &**init_expr {
+                                        if module_exports_sym.as_ref().eq(sym.as_ref()) {
+                                          if let Pat::Ident(BindingIdent {
+                                            id: Ident { sym, .. }, ..
+                                          }) = name
+                                          {
+                                            self.exports_alias.insert(sym.as_ref().to_owned());
+                                            self.walk_body(stmts, false);
+                                            return;
                                           }
                                         }
                                       }
@@ -1450,38 +2960,368 @@ impl CJSLexer {
                                 }
                               }
                             }
-                            _ => {}
                           }
                         }
                       }
                     }
+                    _ => {}
                   }
                 }
-                _ => {}
-              },
-              _ => {}
+              }
             }
           }
         }
         _ => {}
+      },
+      _ => {}
+    }
+  }
+
+  // module.exports = (function (e) { ... })([function (e, t, r) { ... }])
+  fn as_webpack_bundle(&self, expr: &Expr) -> Option<(IndexSet<String>, IndexSet<Reexport>)> {
+    let mut lexer = self.new_sub_lexer();
+    lexer.exports.clear();
+    lexer.reexports.clear();
+    lexer.get_webpack_bundle_exports(expr);
+    if lexer.exports.is_empty() && lexer.reexports.is_empty() {
+      return None;
+    }
+    Some((lexer.exports, lexer.reexports))
+  }
+
+  // walk a function body without touching the current exports,
+  // returns the exports of its return value.
+  fn get_returned_exports(&self, desc: FnDesc, args: &[ExprOrSpread]) -> (IndexSet<String>, IndexSet<Reexport>) {
+    if self.call_depth >= MAX_CALL_DEPTH {
+      return (IndexSet::new(), IndexSet::new());
+    }
+    let mut lexer = self.new_sub_lexer();
+    lexer.call_depth += 1;
+    lexer.exports.clear();
+    lexer.reexports.clear();
+    self.bind_fn_params(&mut lexer, &desc, None, args);
+    lexer.walk(desc.stmts, true);
+    (lexer.exports, lexer.reexports)
+  }
+
+  // walk the body of a local function called by name, bounded by `MAX_CALL_DEPTH`
+  fn walk_fn_call(&mut self, desc: FnDesc, args: &[ExprOrSpread], as_fn: bool) {
+    if self.call_depth >= MAX_CALL_DEPTH {
+      return;
+    }
+    self.call_depth += 1;
+    self.walk_fn_body(desc, None, args, as_fn);
+    self.call_depth -= 1;
+  }
+
+  fn walk_fn_body(&mut self, desc: FnDesc, this_arg: Option<&Expr>, args: &[ExprOrSpread], as_fn: bool) {
+    let mut lexer = self.new_sub_lexer();
+    self.bind_fn_params(&mut lexer, &desc, this_arg, args);
+    self.walk_with(lexer, desc.stmts, as_fn);
+  }
+
+  // new Foo(...args)
+  // walk the constructor with `this` as the exports
+  fn walk_fn_instance(&mut self, desc: FnDesc, args: &[ExprOrSpread]) {
+    if self.call_depth >= MAX_CALL_DEPTH {
+      return;
+    }
+    self.call_depth += 1;
+    let mut lexer = self.new_sub_lexer();
+    self.bind_fn_params(&mut lexer, &desc, None, args);
+    lexer.exports_alias.insert("this".to_owned());
+    lexer.constructing = true;
+    // collect `this.foo = 'bar'`, then check the returned value
+    lexer.walk(desc.stmts.clone(), false);
+    self.walk_with(lexer, desc.stmts, true);
+    self.call_depth -= 1;
+  }
+
+  // new Foo(...args)
+  // reports the instance fields, the properties set by the constructor and the prototype methods
+  fn use_class_instance(&mut self, desc: &ClassDesc, args: &[ExprOrSpread]) {
+    if self.call_depth >= MAX_CALL_DEPTH {
+      return;
+    }
+    let class = &desc.class;
+    if let Some(super_class) = class.super_class.as_ref() {
+      if let Some(super_class) = self.as_class(super_class) {
+        self.call_depth += 1;
+        self.use_class_instance(&super_class, &[]);
+        self.call_depth -= 1;
+      } else if let Some(super_fn) = self.as_function(super_class) {
+        let prototype = super_fn.prototype.clone();
+        self.walk_fn_instance(super_fn, &[]);
+        self.use_prototype_exports(prototype);
+      } else {
+        // class Registry extends EventEmitter {}, the inherited members are unknown
+        self.opaque_instance = true;
+      }
+    }
+    for member in &class.body {
+      if let ClassMember::ClassProp(prop) = member {
+        if !prop.is_static {
+          if let Some(name) = self.as_prop_name(&prop.key) {
+            self.use_export(name, prop.value.as_deref());
+          }
+        }
+      }
+    }
+    for member in &class.body {
+      if let ClassMember::Constructor(Constructor {
+        params,
+        body: Some(body),
+        ..
+      }) = member
+      {
+        let desc = FnDesc {
+          stmts: body.stmts.clone(),
+          params: params
+            .iter()
+            .filter_map(|param| match param {
+              ParamOrTsParamProp::Param(param) => Some(param.pat.clone()),
+              _ => None,
+            })
+            .collect(),
+          is_arrow: false,
+          extends: IndexMap::new(),
+          prototype: IndexMap::new(),
+        };
+        self.walk_fn_instance(desc, args);
+      }
+    }
+    self.use_prototype_exports(self.get_class_prototype(desc));
+  }
+
+  // own properties shadow the prototype members
+  fn use_prototype_exports(&mut self, prototype: IndexMap<String, ValueKind>) {
+    for (name, kind) in prototype {
+      if !self.exports.contains(&name) {
+        self.use_export_kind(name, kind);
+      }
+    }
+  }
+
+  // the static members of a class, including the ones added after its declaration
+  fn get_class_statics(&self, desc: &ClassDesc) -> IndexMap<String, ValueKind> {
+    let mut statics = IndexMap::new();
+    for member in &desc.class.body {
+      let entry = match member {
+        ClassMember::ClassProp(prop) if prop.is_static => self.as_prop_name(&prop.key).map(|name| {
+          let kind = prop
+            .value
+            .as_ref()
+            .map_or(ValueKind::Unknown, |value| self.as_value_kind(value));
+          (name, kind)
+        }),
+        ClassMember::Method(method) if method.is_static => self
+          .as_prop_name(&method.key)
+          .map(|name| (name, get_method_kind(method.kind))),
+        _ => None,
+      };
+      if let Some((name, kind)) = entry {
+        statics.insert(name, kind);
+      }
+    }
+    statics.extend(desc.statics.clone());
+    statics
+  }
+
+  // the methods of a class, including the ones added to its prototype
+  fn get_class_prototype(&self, desc: &ClassDesc) -> IndexMap<String, ValueKind> {
+    let mut prototype = IndexMap::new();
+    for member in &desc.class.body {
+      if let ClassMember::Method(method) = member {
+        if !method.is_static {
+          if let Some(name) = self.as_prop_name(&method.key) {
+            prototype.insert(name, get_method_kind(method.kind));
+          }
+        }
+      }
+    }
+    prototype.extend(desc.prototype.clone());
+    prototype
+  }
+
+  // the names and kinds of the properties of an object literal
+  fn get_object_kinds(&self, props: Vec<PropOrSpread>) -> IndexMap<String, ValueKind> {
+    let mut lexer = self.new_sub_lexer();
+    lexer.clear();
+    lexer.use_object_as_exports(props);
+    lexer
+      .exports
+      .into_iter()
+      .map(|name| {
+        let kind = lexer.kinds.get(&name).copied().unwrap_or(ValueKind::Unknown);
+        (name, kind)
+      })
+      .collect()
+  }
+
+  // Foo.create = function () {}
+  // Foo.prototype.run = function () {}
+  // Object.assign(Foo, { create })
+  fn augment_ident(&mut self, name: &str, prototype: bool, members: IndexMap<String, ValueKind>, replace: bool) {
+    let expr = Expr::Ident(quote_ident(name));
+    if let Some(mut desc) = self.as_function(&expr) {
+      let target = if prototype {
+        &mut desc.prototype
+      } else {
+        &mut desc.extends
+      };
+      if replace {
+        target.clear();
+      }
+      target.extend(members);
+      self.idents.insert(name.into(), IdentKind::Fn(desc));
+    } else if let Some(mut desc) = self.as_class(&expr) {
+      let target = if prototype {
+        &mut desc.prototype
+      } else {
+        &mut desc.statics
+      };
+      if replace {
+        target.clear();
+      }
+      target.extend(members);
+      self.idents.insert(name.into(), IdentKind::Class(desc));
+    }
+  }
+
+  // walk a module function of a bundle, like `function(require, module, exports) { ... }`,
+  // as a standalone commonjs module, bounded by `MAX_CALL_DEPTH`
+  fn walk_bundled_module(&mut self, bundle: Rc<IndexMap<String, BundledModule>>, id: &str) {
+    if self.call_depth >= MAX_CALL_DEPTH {
+      return;
+    }
+    let Some(BundledModule { desc, .. }) = bundle.get(id).cloned() else {
+      return;
+    };
+    let mut lexer = self.new_sub_lexer();
+    lexer.call_depth += 1;
+    lexer.bundle = bundle;
+    lexer.bundle_module = Some(id.to_owned());
+    lexer.idents.clear();
+    lexer.exports_alias = IndexSet::from(["this".to_owned()]);
+    for (param, name) in desc.params.iter().zip(["require", "module", "exports"]) {
+      if let Pat::Ident(BindingIdent { id, .. }) = param {
+        let id = id.sym.as_ref().to_owned();
+        if name.eq("exports") {
+          lexer.exports_alias.insert(id);
+        } else {
+          lexer.idents.insert(id, IdentKind::Builtin(name.to_owned()));
+        }
+      }
+    }
+    self.walk_with(lexer, desc.stmts, false);
+  }
+
+  // define(['exports', 'dep'], function (exports, dep) { exports.a = 1 })
+  // define('name', ['dep'], function (dep) { return { a: 1 } })
+  // define(function (require, exports, module) { ... })
+  // define({ a: 1 })
+  fn walk_amd_define(&mut self, args: &[ExprOrSpread]) {
+    let mut args = args.iter().map(|arg| arg.expr.as_ref()).peekable();
+    // the module id
+    args.next_if(|arg| matches!(arg, Expr::Lit(Lit::Str(_))));
+    let deps = match args.next_if(|arg| matches!(arg, Expr::Array(_))) {
+      Some(Expr::Array(ArrayLit { elems, .. })) => Some(
+        elems
+          .iter()
+          .map(|elem| elem.as_ref().and_then(|elem| self.as_str(&elem.expr)))
+          .collect::<Vec<_>>(),
+      ),
+      _ => None,
+    };
+    let Some(factory) = args.next() else {
+      return;
+    };
+    for dep in deps.iter().flatten().flatten() {
+      if !matches!(dep.as_str(), "require" | "exports" | "module") {
+        self.requires.insert(dep.to_owned());
+      }
+    }
+    let Some(desc) = self.as_function(factory) else {
+      self.reset(factory);
+      return;
+    };
+    // the factory receives `require`, `exports` and `module` if no dependencies are given
+    let deps = deps.unwrap_or_else(|| {
+      ["require", "exports", "module"]
+        .iter()
+        .map(|dep| Some(dep.to_string()))
+        .collect()
+    });
+    let mut lexer = self.new_sub_lexer();
+    lexer.exports_alias.shift_remove("this");
+    for (param, dep) in desc.params.iter().zip(deps) {
+      if let Pat::Ident(BindingIdent { id, .. }) = param {
+        let id = id.sym.as_ref().to_owned();
+        lexer.exports_alias.shift_remove(&id);
+        match dep.as_deref() {
+          Some("exports") => {
+            lexer.idents.shift_remove(&id);
+            lexer.exports_alias.insert(id);
+          }
+          Some(dep @ ("require" | "module")) => {
+            lexer.idents.insert(id, IdentKind::Builtin(dep.to_owned()));
+          }
+          Some(dep) => {
+            lexer.idents.insert(id, IdentKind::Reexport(dep.to_owned()));
+          }
+          None => {
+            lexer.idents.insert(id, IdentKind::Unkonwn);
+          }
+        }
       }
     }
+    // the value returned by the factory is used as `module.exports`
+    lexer.walk(desc.stmts.clone(), false);
+    self.walk_with(lexer, desc.stmts, true);
   }
 
   fn walk_body(&mut self, body: Vec<Stmt>, as_fn: bool) {
-    let mut lexer = CJSLexer {
+    let mut lexer = self.new_sub_lexer();
+    lexer.constructing = self.constructing;
+    self.walk_with(lexer, body, as_fn);
+  }
+
+  fn walk_with(&mut self, mut lexer: CJSLexer, body: Vec<Stmt>, as_fn: bool) {
+    lexer.walk(body, as_fn);
+    self.fn_returned = lexer.fn_returned;
+    self.exports = lexer.exports;
+    self.reexports = lexer.reexports;
+    self.requires = lexer.requires;
+    self.sources = lexer.sources;
+    self.kinds = lexer.kinds;
+    self.exports_kind = lexer.exports_kind;
+    self.prototype = lexer.prototype;
+    self.opaque_instance = lexer.opaque_instance;
+  }
+
+  fn new_sub_lexer(&self) -> CJSLexer {
+    CJSLexer {
       node_env: self.node_env.to_owned(),
       call_mode: false,
+      call_args: vec![],
+      instantiate: false,
       fn_returned: false,
+      constructing: false,
+      call_depth: self.call_depth,
       idents: self.idents.clone(),
       exports_alias: self.exports_alias.clone(),
       exports: self.exports.clone(),
       reexports: self.reexports.clone(),
-    };
-    lexer.walk(body, as_fn);
-    self.fn_returned = lexer.fn_returned;
-    self.exports = lexer.exports;
-    self.reexports = lexer.reexports;
+      requires: self.requires.clone(),
+      sources: self.sources.clone(),
+      kinds: self.kinds.clone(),
+      exports_kind: self.exports_kind,
+      prototype: self.prototype.clone(),
+      opaque_instance: self.opaque_instance,
+      bundle: self.bundle.clone(),
+      bundle_module: self.bundle_module.clone(),
+      loop_budget: self.loop_budget.clone(),
+    }
   }
 }
 
@@ -1506,16 +3346,6 @@ impl Fold for CJSLexer {
   }
 }
 
-fn is_module_ident(expr: &Expr) -> bool {
-  match expr {
-    Expr::Ident(id) => {
-      let id = id.sym.as_ref();
-      id.eq("module")
-    }
-    _ => false,
-  }
-}
-
 fn is_member(expr: &Expr, obj_name: &str, prop_name: &str) -> bool {
   if let Some(member_prop_name) = get_member_prop_name(expr, obj_name) {
     return member_prop_name.eq(prop_name);
@@ -1541,213 +3371,244 @@ fn with_expr_callee(call: &CallExpr) -> Option<&Expr> {
   }
 }
 
-// require('lib')
-fn is_require_call(call: &CallExpr) -> Option<String> {
-  if let Some(Expr::Ident(id)) = with_expr_callee(call) {
-    if id.sym.as_ref().eq("require") && call.args.len() > 0 {
-      return match call.args[0].expr.as_ref() {
-        Expr::Lit(Lit::Str(Str { value, .. })) => Some(value.as_ref().to_owned()),
-        _ => None,
-      };
-    }
-  };
-  None
-}
-
-// match:
-// Object.defineProperty()
-// Object.assgin()
-fn is_object_static_mothod_call(call: &CallExpr, method: &str) -> bool {
-  if let Some(callee) = with_expr_callee(call) {
-    return is_member(callee, "Object", method);
+// match the parameterless iifes webpack wraps its runtime and entry module in:
+// (() => { ... })()
+// (function() { ... })()
+fn is_runtime_iife_call(call: &CallExpr) -> Option<Vec<Stmt>> {
+  match get_iife_call(call)? {
+    (desc, None, []) if desc.params.is_empty() => Some(desc.stmts),
+    _ => None,
   }
-  false
 }
 
-fn is_umd_params(params: &Vec<Pat>) -> bool {
-  if params.len() == 2 {
-    if let Pat::Ident(bid) = &params.get(0).unwrap() {
-      if bid.id.sym.eq("global") {
-        if let Pat::Ident(bid) = &params.get(1).unwrap() {
-          if bid.id.sym.eq("factory") {
-            return true;
+// __webpack_require__.r(__webpack_exports__)
+// __webpack_require__.d(__webpack_exports__, { "default": () => foo, "my-name": () => bar })
+// __webpack_require__.d(__webpack_exports__, "foo", function() { return foo })
+fn get_webpack_define_call(call: &CallExpr) -> Option<(&str, &Expr, Vec<String>)> {
+  let Some(Expr::Member(MemberExpr {
+    obj,
+    prop: MemberProp::Ident(prop),
+    ..
+  })) = with_expr_callee(call)
+  else {
+    return None;
+  };
+  let Expr::Ident(webpack_require) = obj.as_ref() else {
+    return None;
+  };
+  let target = call.args.first()?.expr.as_ref();
+  let names = match prop.sym.as_ref() {
+    "r" if call.args.len() == 1 => vec!["__esModule".to_owned()],
+    "d" => match call.args.get(1)?.expr.as_ref() {
+      Expr::Object(ObjectLit { props, .. }) => {
+        let mut names = vec![];
+        for prop in props {
+          if let PropOrSpread::Prop(prop) = prop {
+            if let Prop::KeyValue(KeyValueProp { key, .. }) = prop.as_ref() {
+              names.push(stringify_prop_name(key)?);
+            }
           }
         }
+        names
       }
-    }
-  }
-  false
+      Expr::Lit(Lit::Str(Str { value, .. })) => vec![value.as_ref().to_owned()],
+      _ => return None,
+    },
+    _ => return None,
+  };
+  Some((webpack_require.sym.as_ref(), target, names))
 }
 
-fn is_string_literal(expr: &Expr, value: &str) -> bool {
-  match &*expr {
-    Expr::Lit(Lit::Str(Str {
-      value: literal_value, ..
-    })) => literal_value.eq(value),
+// this instanceof Foo
+fn is_this_instanceof(expr: &Expr) -> bool {
+  match unwrap_paren(expr) {
+    Expr::Bin(BinExpr {
+      op: BinaryOp::InstanceOf,
+      left,
+      ..
+    }) => matches!(unwrap_paren(left), Expr::This(_)),
     _ => false,
   }
 }
 
-fn is_typeof(expr: &Expr, identifier: &str) -> bool {
-  match &*expr {
-    Expr::Unary(UnaryExpr { arg, op, .. }) => match op {
-      UnaryOp::TypeOf => match &**arg {
-        Expr::Ident(ident) => ident.sym.eq(identifier),
-        _ => false,
-      },
-      _ => false,
-    },
-    _ => false,
+// return e.r(t), e.d(t, { foo: () => n }), t
+fn get_returned_ident(stmts: &[Stmt]) -> Option<&str> {
+  let Some(Stmt::Return(ReturnStmt { arg: Some(arg), .. })) = stmts.last() else {
+    return None;
+  };
+  let last = match unwrap_paren(arg) {
+    Expr::Seq(SeqExpr { exprs, .. }) => exprs.last()?.as_ref(),
+    expr => expr,
+  };
+  match unwrap_paren(last) {
+    Expr::Ident(id) => Some(id.sym.as_ref()),
+    _ => None,
   }
 }
 
-fn is_umd_exports_check(expr: &Expr) -> bool {
-  match &*expr {
-    Expr::Bin(BinExpr { left, right, op, .. }) => match op {
-      BinaryOp::EqEq | BinaryOp::EqEqEq => {
-        (is_typeof(&left, "exports") && is_string_literal(&right, "object"))
-          || (is_typeof(&right, "exports") && is_string_literal(&left, "object"))
-      }
-      _ => false,
-    },
-    _ => false,
+fn unwrap_paren(expr: &Expr) -> &Expr {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => unwrap_paren(expr),
+    _ => expr,
   }
 }
 
-fn is_umd_module_check(expr: &Expr) -> bool {
-  match &*expr {
-    Expr::Bin(BinExpr { left, right, op, .. }) => match op {
-      BinaryOp::EqEq | BinaryOp::EqEqEq => {
-        (is_typeof(&left, "module") && is_string_literal(&right, "object"))
-          || (is_typeof(&right, "module") && is_string_literal(&left, "object"))
-      }
-      BinaryOp::NotEq | BinaryOp::NotEqEq => {
-        (is_typeof(&left, "module") && is_string_literal(&right, "undefined"))
-          || (is_typeof(&right, "module") && is_string_literal(&left, "undefined"))
-      }
-      _ => false,
-    },
+// (this && this.__exportStar)
+fn is_this_helper_check(expr: &Expr) -> bool {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => is_this_helper_check(expr),
+    Expr::Bin(BinExpr {
+      op: BinaryOp::LogicalAnd,
+      left,
+      right,
+      ..
+    }) => {
+      matches!(left.as_ref(), Expr::This(_))
+        && matches!(right.as_ref(), Expr::Member(MemberExpr { obj, .. }) if matches!(obj.as_ref(), Expr::This(_)))
+    }
     _ => false,
   }
 }
 
-fn is_umd_define_check(expr: &Expr) -> bool {
-  match &*expr {
-    Expr::Bin(BinExpr { left, right, op, .. }) => match op {
-      BinaryOp::EqEq | BinaryOp::EqEqEq => {
-        (is_typeof(&left, "define") && is_string_literal(&right, "function"))
-          || (is_typeof(&right, "define") && is_string_literal(&left, "function"))
-      }
-      _ => false,
-    },
-    _ => false,
+// for (var key in obj)
+// for (const key of keys)
+fn get_for_head_name(head: &ForHead) -> Option<&str> {
+  let pat = match head {
+    ForHead::VarDecl(var) => &var.decls.first()?.name,
+    ForHead::Pat(pat) => pat.as_ref(),
+    _ => return None,
+  };
+  match pat {
+    Pat::Ident(BindingIdent { id, .. }) => Some(id.sym.as_ref()),
+    _ => None,
   }
 }
 
-// if ('object' == typeof exports && 'object' == typeof module)
-// module.exports = t(require('react'));
-// else if ('function' == typeof define && define.amd) define(['react'], t);
-// else {
-// var r = 'object' == typeof exports ? t(require('react')) : t(e.react);
-// for (var n in r) ('object' == typeof exports ? exports : e)[n] = r[n];
-// }
-fn is_umd_checks(stmts: &Vec<Stmt>) -> bool {
-  match stmts.get(0) {
-    Some(stmt) => match stmt {
-      // TODO: handle ternary version
-      // !(function (e, t) {
-      //   "object" == typeof exports && "undefined" != typeof module
-      //     ? t(exports)
-      //     : "function" == typeof define && define.amd
-      //     ? define(["exports"], t)
-      //     : t(
-      //         ((e =
-      //           "undefined" != typeof globalThis
-      //             ? globalThis
-      //             : e || self).rudderanalytics = {})
-      //       );
-      // })(this, function (e) {
-      Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
-        Expr::Cond(CondExpr { test, alt, .. }) => match &**test {
-          Expr::Bin(BinExpr { left, right, op, .. }) => {
-            if matches!(op, BinaryOp::LogicalAnd) {
-              if (is_umd_exports_check(&left) && is_umd_module_check(&right))
-                || (is_umd_exports_check(&right) && is_umd_module_check(&left))
-              {
-                match &**alt {
-                  Expr::Cond(CondExpr { test, .. }) => match &**test {
-                    Expr::Bin(BinExpr { left, op, .. }) => {
-                      if matches!(op, BinaryOp::LogicalAnd) && is_umd_define_check(&left) {
-                        return true;
-                      }
-                      return false;
-                    }
-                    _ => false,
-                  },
-                  _ => false,
-                };
-              }
-              return false;
-            }
-            return false;
-          }
-          _ => false,
+fn is_iife_call(call: &CallExpr) -> Option<Vec<Stmt>> {
+  get_iife_call(call).map(|(desc, ..)| desc.stmts)
+}
+
+// match:
+// (function() { ... })(...args)
+// (() => { ... })(...args)
+// (function() { ... }).call(this, ...args)
+fn get_iife_call(call: &CallExpr) -> Option<(FnDesc, Option<&Expr>, &[ExprOrSpread])> {
+  let callee = match with_expr_callee(call)? {
+    Expr::Paren(ParenExpr { expr, .. }) => expr.as_ref(),
+    callee => callee,
+  };
+  match callee {
+    Expr::Fn(FnExpr { function, .. }) => Some((get_fn_desc(function)?, None, &call.args)),
+    Expr::Arrow(arrow) => Some((get_arrow_fn_desc(arrow), None, &call.args)),
+    Expr::Member(MemberExpr {
+      obj,
+      prop: MemberProp::Ident(prop),
+      ..
+    }) if prop.sym.as_ref().eq("call") => {
+      let desc = match obj.as_ref() {
+        Expr::Paren(ParenExpr { expr, .. }) => match expr.as_ref() {
+          Expr::Fn(FnExpr { function, .. }) => get_fn_desc(function)?,
+          Expr::Arrow(arrow) => get_arrow_fn_desc(arrow),
+          _ => return None,
         },
-        _ => false,
-      },
-      Stmt::If(IfStmt { test, alt, .. }) => match &**test {
-        Expr::Bin(BinExpr { left, right, op, .. }) => {
-          if matches!(op, BinaryOp::LogicalAnd) {
-            if (is_umd_exports_check(&left) && is_umd_module_check(&right))
-              || (is_umd_exports_check(&right) && is_umd_module_check(&left))
-            {
-              match &*alt {
-                Some(alt_stmt) => match &**alt_stmt {
-                  Stmt::If(IfStmt { test, .. }) => match &**test {
-                    Expr::Bin(BinExpr { left, op, .. }) => {
-                      if matches!(op, BinaryOp::LogicalAnd) && is_umd_define_check(&left) {
-                        return true;
-                      }
-                      return false;
-                    }
-                    _ => false,
-                  },
-                  _ => false,
-                },
-                _ => false,
-              };
+        _ => return None,
+      };
+      let this_arg = call.args.first().map(|arg| arg.expr.as_ref());
+      Some((desc, this_arg, call.args.get(1..).unwrap_or_default()))
+    }
+    _ => None,
+  }
+}
+
+// browserify bundle:
+// (function() { function r(e, n, t) { ... } return r })()({
+//   1: [function(require, module, exports) { ... }, { "./dep": 2 }],
+//   2: [function(require, module, exports) { ... }, {}],
+// }, {}, [1])(1)
+// returns the bundled modules by id and the id of the entry module
+fn get_browserify_bundle(expr: &Expr) -> Option<(IndexMap<String, BundledModule>, String)> {
+  let call = match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => return get_browserify_bundle(expr),
+    Expr::Call(call) => call,
+    _ => return None,
+  };
+  let bundle = match with_expr_callee(call)? {
+    Expr::Call(bundle) => bundle,
+    Expr::Paren(ParenExpr { expr, .. }) => match expr.as_ref() {
+      Expr::Call(bundle) => bundle,
+      _ => return None,
+    },
+    _ => return None,
+  };
+  if bundle.args.len() != 3 {
+    return None;
+  }
+  if let (Expr::Object(ObjectLit { props, .. }), Expr::Array(ArrayLit { elems: entries, .. })) =
+    (bundle.args[0].expr.as_ref(), bundle.args[2].expr.as_ref())
+  {
+    // the trailing `(1)` selects the entry, otherwise the last of the entries
+    let entry = match call.args.first() {
+      Some(arg) => as_bundle_id(&arg.expr)?,
+      None => as_bundle_id(&entries.last()?.as_ref()?.expr)?,
+    };
+    let mut modules = IndexMap::new();
+    for prop in props {
+      if let PropOrSpread::Prop(prop) = prop {
+        if let Prop::KeyValue(KeyValueProp { key, value }) = prop.as_ref() {
+          let key = match key {
+            PropName::Num(Number { value, .. }) => Some(value.to_string()),
+            _ => stringify_prop_name(key),
+          };
+          if let (Some(key), Expr::Array(ArrayLit { elems, .. })) = (key, value.as_ref()) {
+            let desc = match elems.first() {
+              Some(Some(ExprOrSpread { expr, .. })) => match expr.as_ref() {
+                Expr::Fn(FnExpr { function, .. }) => get_fn_desc(function),
+                _ => None,
+              },
+              _ => None,
+            };
+            let deps = match elems.get(1) {
+              Some(Some(ExprOrSpread { expr, .. })) => match expr.as_ref() {
+                Expr::Object(ObjectLit { props, .. }) => get_bundle_deps(props),
+                _ => IndexMap::new(),
+              },
+              _ => IndexMap::new(),
+            };
+            if let Some(desc) = desc {
+              modules.insert(key, BundledModule { desc, deps });
             }
-            return false;
           }
-          return false;
         }
-        _ => false,
-      },
-      _ => false,
-    },
-    None => false,
+      }
+    }
+    if modules.contains_key(&entry) {
+      return Some((modules, entry));
+    }
   }
+  None
 }
 
-fn is_iife_call(call: &CallExpr) -> Option<Vec<Stmt>> {
-  let expr = if let Some(callee) = with_expr_callee(call) {
-    match callee {
-      Expr::Paren(ParenExpr { expr, .. }) => expr.as_ref(),
-      _ => callee,
-    }
-  } else {
-    return None;
-  };
-  match expr {
-    Expr::Fn(func) => {
-      if let Some(BlockStmt { stmts, .. }) = &func.function.body {
-        return Some(stmts.clone());
+// { "./dep": 2, "ignored": false }
+fn get_bundle_deps(props: &[PropOrSpread]) -> IndexMap<String, String> {
+  let mut deps = IndexMap::new();
+  for prop in props {
+    if let PropOrSpread::Prop(prop) = prop {
+      if let Prop::KeyValue(KeyValueProp { key, value }) = prop.as_ref() {
+        if let (Some(key), Some(id)) = (stringify_prop_name(key), as_bundle_id(value)) {
+          deps.insert(key, id);
+        }
       }
     }
-    Expr::Arrow(arrow) => return Some(get_arrow_body_as_stmts(arrow)),
-    _ => {}
   }
-  None
+  deps
+}
+
+fn as_bundle_id(expr: &Expr) -> Option<String> {
+  match expr {
+    Expr::Lit(Lit::Num(Number { value, .. })) => Some(value.to_string()),
+    Expr::Lit(Lit::Str(Str { value, .. })) => Some(value.as_ref().to_owned()),
+    _ => None,
+  }
 }
 
 fn is_export_call(call: &CallExpr) -> bool {
@@ -1821,6 +3682,27 @@ fn get_member_expr_from_assign_target(v: &AssignTarget) -> Option<&MemberExpr> {
   }
 }
 
+fn get_fn_desc(function: &Function) -> Option<FnDesc> {
+  let body = function.body.as_ref()?;
+  Some(FnDesc {
+    stmts: body.stmts.clone(),
+    params: function.params.iter().map(|param| param.pat.clone()).collect(),
+    is_arrow: false,
+    extends: IndexMap::new(),
+    prototype: IndexMap::new(),
+  })
+}
+
+fn get_arrow_fn_desc(arrow: &ArrowExpr) -> FnDesc {
+  FnDesc {
+    stmts: get_arrow_body_as_stmts(arrow),
+    params: arrow.params.clone(),
+    is_arrow: true,
+    extends: IndexMap::new(),
+    prototype: IndexMap::new(),
+  }
+}
+
 fn get_arrow_body_as_stmts(arrow: &ArrowExpr) -> Vec<Stmt> {
   match &*arrow.body {
     BlockStmtOrExpr::BlockStmt(BlockStmt { stmts, .. }) => stmts.clone(),
@@ -1842,32 +3724,40 @@ fn get_member_prop_name(expr: &Expr, obj_name: &str) -> Option<String> {
   None
 }
 
-fn get_class_static_names(class: &Class) -> Vec<String> {
-  class
-    .body
-    .iter()
-    .filter(|&member| match member {
-      ClassMember::ClassProp(prop) => prop.is_static,
-      ClassMember::Method(method) => method.is_static,
-      _ => false,
-    })
-    .map(|member| {
-      match member {
-        ClassMember::ClassProp(prop) => {
-          if let PropName::Ident(id) = &prop.key {
-            return id.sym.as_ref().into();
-          }
-        }
-        ClassMember::Method(method) => {
-          if let PropName::Ident(id) = &method.key {
-            return id.sym.as_ref().into();
-          }
-        }
-        _ => {}
-      };
-      "".to_owned()
-    })
-    .collect()
+// the value returned by a getter, like `function () { return foo }`
+fn get_returned_expr(stmts: &[Stmt]) -> Option<Expr> {
+  stmts.iter().find_map(|stmt| match stmt {
+    Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => Some(arg.as_ref().clone()),
+    _ => None,
+  })
+}
+
+// Foo.prototype
+fn get_prototype_owner(expr: &Expr) -> Option<&str> {
+  match expr {
+    Expr::Member(MemberExpr { obj, prop, .. }) if get_prop_name(prop).as_deref() == Some("prototype") => {
+      match obj.as_ref() {
+        Expr::Ident(id) => Some(id.sym.as_ref()),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+fn get_method_kind(kind: MethodKind) -> ValueKind {
+  match kind {
+    MethodKind::Method => ValueKind::Function,
+    _ => ValueKind::Unknown,
+  }
+}
+
+fn get_class_desc(class: &Class) -> ClassDesc {
+  ClassDesc {
+    class: class.clone(),
+    statics: IndexMap::new(),
+    prototype: IndexMap::new(),
+  }
 }
 
 fn get_prop_name(prop: &MemberProp) -> Option<String> {
@@ -1882,6 +3772,25 @@ fn get_prop_name(prop: &MemberProp) -> Option<String> {
   }
 }
 
+// { let a = 1; const b = 2; class C {} }
+fn get_block_scoped_names(stmts: &[Stmt]) -> Vec<String> {
+  let mut names = vec![];
+  for stmt in stmts {
+    match stmt {
+      Stmt::Decl(Decl::Var(var)) if var.kind != VarDeclKind::Var => {
+        for decl in &var.decls {
+          if let Pat::Ident(BindingIdent { id, .. }) = &decl.name {
+            names.push(id.sym.as_ref().to_owned());
+          }
+        }
+      }
+      Stmt::Decl(Decl::Class(ClassDecl { ident, .. })) => names.push(ident.sym.as_ref().to_owned()),
+      _ => {}
+    }
+  }
+  names
+}
+
 fn stringify_prop_name(name: &PropName) -> Option<String> {
   match name {
     PropName::Ident(id) => Some(id.sym.as_ref().into()),
@@ -1,47 +1,92 @@
+mod builtin;
 mod cjs;
 mod error;
 mod swc;
 mod test;
 
+use builtin::{parse_node_version, Specifier};
+use cjs::{CallArg, ExportSource, Reexport, ValueKind};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use swc::SWC;
-use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+use swc::{CallMode, Interop, SWC};
+use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Options {
   node_env: Option<String>,
   call_mode: Option<bool>,
+  call_args: Option<Vec<CallArg>>,
+  instantiate: Option<bool>,
+  node_version: Option<String>,
+  builtin_exports: Option<bool>,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Output {
   pub exports: Vec<String>,
-  pub reexports: Vec<String>,
+  pub reexports: Vec<Reexport>,
+  pub requires: Vec<String>,
+  pub sources: IndexMap<String, ExportSource>,
+  pub kinds: IndexMap<String, ValueKind>,
+  pub exports_kind: ValueKind,
+  pub prototype: Vec<String>,
+  pub opaque_instance: bool,
+  pub es_module: bool,
+  pub has_default: bool,
+  pub default_kind: Option<ValueKind>,
+  pub interop: Interop,
+  pub specifiers: IndexMap<String, Specifier>,
 }
 
 #[wasm_bindgen(js_name = "parse")]
 pub fn parse(specifier: &str, code: &str, options: JsValue) -> Result<JsValue, JsValue> {
   console_error_panic_hook::set_once();
 
-  let options: Options = serde_wasm_bindgen::from_value(options).unwrap_or(Options{
-    node_env: None,
-    call_mode: None,
-  });
+  let options: Options = if options.is_undefined() || options.is_null() {
+    Options::default()
+  } else {
+    serde_wasm_bindgen::from_value(options).map_err(|err| JsError::new(&err.to_string()))?
+  };
+  let node_version = match options.node_version.as_deref() {
+    Some(version) => parse_node_version(version).map_err(|err| JsError::new(&err))?,
+    None => None,
+  };
   let swc = SWC::parse(specifier, code).expect("could not parse module");
   let node_env = if let Some(env) = options.node_env {
     env
   } else {
     "production".to_owned()
   };
-  let call_mode = if let Some(ok) = options.call_mode { ok } else { false };
-  let (exports, reexports) = swc.parse_cjs_exports(node_env.as_str(), call_mode).unwrap();
-  Ok(
-    serde_wasm_bindgen::to_value(&Output {
-      exports: exports,
-      reexports: reexports,
+  let instantiate = options.instantiate.unwrap_or(false);
+  let call_mode = if options.call_mode.unwrap_or(false) || instantiate {
+    Some(CallMode {
+      args: options.call_args.unwrap_or_default(),
+      instantiate,
     })
+  } else {
+    None
+  };
+  let analysis = swc.analyze_cjs_with_call_mode(node_env.as_str(), call_mode).unwrap();
+  let specifiers = analysis.get_specifiers(node_version, options.builtin_exports.unwrap_or(false));
+  Ok(
+    Output {
+      exports: analysis.exports,
+      reexports: analysis.reexports,
+      requires: analysis.requires,
+      sources: analysis.sources,
+      kinds: analysis.kinds,
+      exports_kind: analysis.exports_kind,
+      prototype: analysis.prototype,
+      opaque_instance: analysis.opaque_instance,
+      es_module: analysis.es_module,
+      has_default: analysis.has_default,
+      default_kind: analysis.default_kind,
+      interop: analysis.interop,
+      specifiers,
+    }
+    .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
     .unwrap(),
   )
 }
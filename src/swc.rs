@@ -1,20 +1,89 @@
-use crate::cjs::CJSLexer;
+use crate::builtin::{classify_specifier, NodeVersion, Specifier};
+use crate::cjs::{CJSLexer, CallArg, ExportSource, Reexport, ValueKind, MAX_LOOP_ITERATIONS};
 use crate::error::{DiagnosticBuffer, ErrorBuffer};
 
 use indexmap::{IndexMap, IndexSet};
-use std::{path::Path, rc::Rc};
+use serde::Serialize;
+use std::{cell::Cell, path::Path, rc::Rc};
 use swc_common::{
   comments::SingleThreadedComments,
   errors::{Handler, HandlerFlags},
   FileName, SourceMap,
 };
-use swc_ecma_ast::EsVersion;
+use swc_ecma_ast::{EsVersion, ExprOrSpread};
 use swc_ecmascript::{
   ast::{Module, Program},
   parser::{lexer::Lexer, EsConfig, StringInput, Syntax},
   visit::FoldWith,
 };
 
+/// the analysis of a cjs module.
+pub struct CJSAnalysis {
+  pub exports: Vec<String>,
+  pub reexports: Vec<Reexport>,
+  /// the dependencies of an AMD module
+  pub requires: Vec<String>,
+  /// the modules that exports are bound to, by export name
+  pub sources: IndexMap<String, ExportSource>,
+  /// the kinds of the exported values, by export name
+  pub kinds: IndexMap<String, ValueKind>,
+  /// the kind of `module.exports` itself
+  pub exports_kind: ValueKind,
+  /// the prototype members of a class or function assigned to `module.exports`
+  pub prototype: Vec<String>,
+  /// whether `module.exports` is an instance of a class that is not declared in the module, or that extends one
+  pub opaque_instance: bool,
+  /// whether the module is marked with `__esModule`
+  pub es_module: bool,
+  /// whether the module has a `default` export
+  pub has_default: bool,
+  /// the kind of the default import under the recommended interop, `None` if it is `undefined`
+  pub default_kind: Option<ValueKind>,
+  /// how the module should be imported from esm
+  pub interop: Interop,
+}
+
+impl CJSAnalysis {
+  /// classify the specifiers of the reexports, sources and requires, `node_version` defaults to the latest node.
+  pub fn get_specifiers(
+    &self,
+    node_version: Option<NodeVersion>,
+    builtin_exports: bool,
+  ) -> IndexMap<String, Specifier> {
+    let mut specifiers = IndexMap::new();
+    let names = self
+      .reexports
+      .iter()
+      .map(|reexport| &reexport.specifier)
+      .chain(self.sources.values().map(|source| &source.specifier))
+      .chain(self.requires.iter());
+    for name in names {
+      if !specifiers.contains_key(name) {
+        specifiers.insert(name.to_owned(), classify_specifier(name, node_version, builtin_exports));
+      }
+    }
+    specifiers
+  }
+}
+
+/// how `module.exports` is called in call mode.
+#[derive(Clone, Debug, Default)]
+pub struct CallMode {
+  pub args: Vec<CallArg>,
+  /// call `module.exports` with `new`
+  pub instantiate: bool,
+}
+
+/// the interop used to import a cjs module from esm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interop {
+  /// the default import is `module.exports`, as in node
+  Node,
+  /// the default import is `exports.default` for modules marked with `__esModule`, as in babel and bundlers
+  Babel,
+}
+
 pub struct SWC {
   pub specifier: String,
   pub module: Module,
@@ -60,26 +129,93 @@ impl SWC {
   }
 
   /// parse export names in the cjs module.
+  #[cfg(test)]
   pub fn parse_cjs_exports(
     &self,
     node_env: &str,
     call_mode: bool,
   ) -> Result<(Vec<String>, Vec<String>), anyhow::Error> {
+    let analysis = self.analyze_cjs(node_env, call_mode)?;
+    Ok((
+      analysis.exports,
+      analysis.reexports.iter().map(|reexport| reexport.to_string()).collect(),
+    ))
+  }
+
+  /// analyze the exports and dependencies of the cjs module.
+  #[cfg(test)]
+  pub fn analyze_cjs(&self, node_env: &str, call_mode: bool) -> Result<CJSAnalysis, anyhow::Error> {
+    self.analyze_cjs_with_call_mode(node_env, call_mode.then(CallMode::default))
+  }
+
+  /// analyze the cjs module, calling `module.exports` with the given arguments.
+  pub fn analyze_cjs_with_call_mode(
+    &self,
+    node_env: &str,
+    call_mode: Option<CallMode>,
+  ) -> Result<CJSAnalysis, anyhow::Error> {
+    let CallMode { args, instantiate } = call_mode.clone().unwrap_or_default();
     let mut lexer = CJSLexer {
       node_env: node_env.to_owned(),
-      call_mode: call_mode,
+      call_mode: call_mode.is_some(),
+      call_args: args
+        .iter()
+        .map(|arg| ExprOrSpread {
+          spread: None,
+          expr: Box::new(arg.to_expr()),
+        })
+        .collect(),
+      instantiate,
       fn_returned: false,
+      constructing: false,
+      call_depth: 0,
       idents: IndexMap::new(),
-      exports_alias: IndexSet::new(),
+      // top-level `this` is `module.exports`
+      exports_alias: IndexSet::from(["this".to_owned()]),
       exports: IndexSet::new(),
       reexports: IndexSet::new(),
+      requires: IndexSet::new(),
+      sources: IndexMap::new(),
+      kinds: IndexMap::new(),
+      exports_kind: ValueKind::Object,
+      prototype: IndexSet::new(),
+      opaque_instance: false,
+      bundle: Rc::default(),
+      bundle_module: None,
+      loop_budget: Rc::new(Cell::new(MAX_LOOP_ITERATIONS)),
     };
     let program = Program::Module(self.module.clone());
     program.fold_with(&mut lexer);
-    Ok((
-      lexer.exports.into_iter().collect(),
-      lexer.reexports.into_iter().collect(),
-    ))
+    let exports = &lexer.exports;
+    let sources: IndexMap<String, ExportSource> = exports
+      .iter()
+      .filter_map(|name| Some((name.to_owned(), lexer.sources.get(name)?.clone())))
+      .collect();
+    let kinds: IndexMap<String, ValueKind> = exports
+      .iter()
+      .map(|name| (name.to_owned(), *lexer.kinds.get(name).unwrap_or(&ValueKind::Unknown)))
+      .collect();
+    let es_module = exports.contains("__esModule");
+    let has_default = exports.contains("default");
+    let (interop, default_kind) = if es_module {
+      (Interop::Babel, kinds.get("default").copied())
+    } else {
+      (Interop::Node, Some(lexer.exports_kind))
+    };
+    Ok(CJSAnalysis {
+      exports: lexer.exports.into_iter().collect(),
+      reexports: lexer.reexports.into_iter().collect(),
+      requires: lexer.requires.into_iter().collect(),
+      sources,
+      kinds,
+      exports_kind: lexer.exports_kind,
+      prototype: lexer.prototype.into_iter().collect(),
+      opaque_instance: lexer.opaque_instance,
+      es_module,
+      has_default,
+      default_kind,
+      interop,
+    })
   }
 }
 
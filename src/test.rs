@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-  use crate::swc::SWC;
+  use crate::builtin::{parse_node_version, Specifier, SpecifierKind};
+  use crate::cjs::{ArgNumber, CallArg, ExportSource, Reexport, ReexportArg, ReexportKind, ValueKind};
+  use crate::swc::{CallMode, Interop, SWC};
 
   #[test]
   fn parse_cjs_exports_case_1() {
@@ -1483,4 +1485,2252 @@ mod tests {
       .expect("could not parse exports");
     assert_eq!(exports.join(","), "foo,bar");
   }
+
+  #[test]
+  fn parse_cjs_exports_case_25() {
+    let source = r#"
+      const KEY = 'key'
+      module.exports = {
+        get version() { return '1.0.0' },
+        set debug(value) {},
+        [KEY]: 1,
+        ['lit']: 2,
+        [0]: 3,
+      }
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "version,debug,key,lit,0");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_25_1() {
+    let source = r#"
+      const a = { foo: 1 }
+      const b = { bar: 1 }
+      function fn() {
+        return { baz: 1 }
+      }
+      const arrow = () => ({ qux: 1 })
+      module.exports = {
+        ...fn(),
+        ...arrow(),
+        ...(process.env.NODE_ENV === 'production' ? a : b),
+        ...Object.assign({}, b, { ...require('lib') }),
+      }
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "baz,qux,foo,bar");
+    assert_eq!(reexports.join(","), "lib");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_26() {
+    let source = r#"
+      const desc = { value: 1 }
+      Object.defineProperties(exports, {
+        a: { get() { return 1 } },
+        b: { value: 1 },
+        ['c']: desc,
+        d: { enumerable: true },
+      })
+      Reflect.defineProperty(exports, 'e', { get: () => 1 })
+      Reflect.defineProperty(module.exports, 'f', { value: 1 })
+      exports.__defineGetter__('g', function () { return 1 })
+      module.exports.__defineGetter__('h', () => 1)
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b,c,e,f,g,h");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_26_1() {
+    let source = r#"
+      exports.nope = 1
+      Object.defineProperties(module, {
+        exports: { value: { foo: 'bar' } },
+      })
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_27() {
+    let source = r#"
+      var d = Object.defineProperty;
+      var O = Object;
+      var { defineProperties: dp } = Object;
+      const assign = O.assign;
+      var e = exports;
+      d(exports, 'a', { get: function () { return 1 } });
+      (0, d)(module.exports, 'b', { value: 1 });
+      dp(e, { c: { value: 1 } });
+      assign(module.exports, { foo: 'bar' }, require('lib'));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b,c,foo");
+    assert_eq!(reexports.join(","), "lib");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_27_1() {
+    let source = r#"
+      var r = require;
+      module.exports = r("lib");
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (_, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(reexports.join(","), "lib");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_28() {
+    let source = r#"
+      this.foo = 'bar';
+      (() => {
+        this.bar = 123;
+      })();
+      (function () {
+        this.nope = true;
+      })();
+      (function () {
+        this.baz = true;
+      }).call(this);
+      (function (root) {
+        root.qux = true;
+      })(this);
+      function Fn() {
+        this.nope2 = true;
+      }
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar,baz,qux");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_28_1() {
+    let source = r#"
+      Object.defineProperty(this, '__esModule', { value: true });
+      var e = { foo: 1 };
+      (function (e) {
+        e.bar = true;
+      })(e);
+      (function (e) {
+        e.baz = true;
+      })(exports);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,baz");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_29() {
+    let source = r#"
+      function init() {
+        exports.a = 1;
+        exports.b = 2;
+        more();
+      }
+      function more() {
+        module.exports.c = 3;
+      }
+      function setup(e, m) {
+        e.d = 4;
+        m.exports.e = 5;
+      }
+      function unused() {
+        exports.nope = true;
+      }
+      function loop() {
+        exports.f = 6;
+        loop();
+      }
+      init();
+      setup(module.exports, module);
+      loop();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b,c,d,e,f");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_29_1() {
+    let source = r#"
+      const setup = (exports, name = 'bar', enabled) => {
+        exports[name] = true;
+        if (enabled) {
+          exports.nope = true;
+        }
+      };
+      setup({});
+      setup(exports);
+      (function (exports) {
+        exports.nope = true;
+      })({});
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_29_2() {
+    // `exports` shadowed in a block only
+    let source = r#"
+      exports.outer = 1;
+      if (true) {
+        let exports = {};
+        exports.shadow = 1;
+      }
+      {
+        const exports = {};
+        exports.shadow2 = 1;
+      }
+      exports.after = 1;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "outer,after");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_30() {
+    // browserify --standalone output
+    let source = r#"
+    (function(f){if(typeof exports==="object"&&typeof module!=="undefined"){module.exports=f()}else if(typeof define==="function"&&define.amd){define([],f)}else{var g;if(typeof window!=="undefined"){g=window}else if(typeof global!=="undefined"){g=global}else if(typeof self!=="undefined"){g=self}else{g=this}g.myLib = f()}})(function(){var define,module,exports;return (function(){function r(e,n,t){function o(i,f){if(!n[i]){if(!e[i]){var c="function"==typeof require&&require;if(!f&&c)return c(i,!0);if(u)return u(i,!0);var a=new Error("Cannot find module '"+i+"'");throw a.code="MODULE_NOT_FOUND",a}var p=n[i]={exports:{}};e[i][0].call(p.exports,function(r){var n=e[i][1][r];return o(n||r)},p,p.exports,r,e,n,t)}return n[i].exports}for(var u="function"==typeof require&&require,i=0;i<t.length;i++)o(t[i]);return o}return r})()({1:[function(require,module,exports){
+    var dep = require('./dep');
+    exports.foo = function () {};
+    module.exports.bar = dep;
+    },{"./dep":2}],2:[function(require,module,exports){
+    exports.nope = true;
+    },{}]},{},[1])(1)
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_30_1() {
+    // minified browserify --standalone output
+    let source = r#"
+    !function(e){if("object"==typeof exports&&"undefined"!=typeof module)module.exports=e();else if("function"==typeof define&&define.amd)define([],e);else{("undefined"!=typeof window?window:"undefined"!=typeof global?global:"undefined"!=typeof self?self:this).myLib=e()}}(function(){return function(){function r(e,n,t){function o(i,f){if(!n[i]){if(!e[i]){var c="function"==typeof require&&require;if(!f&&c)return c(i,!0);if(u)return u(i,!0);var a=new Error("Cannot find module '"+i+"'");throw a.code="MODULE_NOT_FOUND",a}var p=n[i]={exports:{}};e[i][0].call(p.exports,function(r){var n=e[i][1][r];return o(n||r)},p,p.exports,r,e,n,t)}return n[i].exports}for(var u="function"==typeof require&&require,i=0;i<t.length;i++)o(t[i]);return o}return r}()({1:[function(e,t,n){t.exports={nope:!0}},{}],2:[function(e,t,n){"use strict";t.exports={a:1,b:e("./1")}},{"./1":1}]},{},[2])(2)});
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_30_2() {
+    // the entry of a browserify bundle reexports a bundled module
+    let source = r#"
+    !function(e){if("object"==typeof exports&&"undefined"!=typeof module)module.exports=e();else if("function"==typeof define&&define.amd)define([],e);else{("undefined"!=typeof window?window:"undefined"!=typeof global?global:"undefined"!=typeof self?self:this).myLib=e()}}(function(){return function(){function r(e,n,t){function o(i,f){if(!n[i]){if(!e[i]){var c="function"==typeof require&&require;if(!f&&c)return c(i,!0);if(u)return u(i,!0);var a=new Error("Cannot find module '"+i+"'");throw a.code="MODULE_NOT_FOUND",a}var p=n[i]={exports:{}};e[i][0].call(p.exports,function(r){var n=e[i][1][r];return o(n||r)},p,p.exports,r,e,n,t)}return n[i].exports}for(var u="function"==typeof require&&require,i=0;i<t.length;i++)o(t[i]);return o}return r}()({1:[function(require,module,exports){
+    module.exports = require('./lib');
+    },{"./lib":2}],2:[function(require,module,exports){
+    exports.foo = require('./util').foo;
+    exports.ext = require('ext');
+    },{"./util":3,"ext":false}],3:[function(require,module,exports){
+    exports.foo = 1;
+    },{}]},{},[1,3])(1)});
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,ext");
+    assert!(analysis.reexports.is_empty());
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+    assert_eq!(analysis.sources.keys().cloned().collect::<Vec<_>>().join(","), "ext");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_31() {
+    let source = r#"
+    (function (global, factory) {
+      typeof exports === 'object' && typeof module !== 'undefined' ? module.exports = factory(require('dep')) :
+      typeof define === 'function' && define.amd ? define(['dep'], factory) :
+      (global = global || self, global.myLib = factory(global.dep));
+    }(this, function (dep) {
+      'use strict';
+      function foo() {}
+      return { foo: foo, bar: dep.bar, version: '1.0.0' };
+    }));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar,version");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_31_1() {
+    // minified umd
+    let source = r#"
+    !function(e,t){"object"==typeof exports&&"undefined"!=typeof module?module.exports=t():"function"==typeof define&&define.amd?define(t):(e=e||self).myLib=t()}(this,function(){"use strict";function e(){}return e.foo=1,{a:e,b:2}});
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_31_2() {
+    // umd with arrow function wrappers
+    let source = r#"
+    ((root, factory) => {
+      if (typeof exports === 'object' && typeof module === 'object') {
+        module.exports = factory(require('react'));
+      } else if (typeof define === 'function' && define.amd) {
+        define(['react'], factory);
+      } else {
+        root.myLib = factory(root.React);
+      }
+    })(this, (React) => {
+      const Button = () => null;
+      return { Button, createElement: React.createElement };
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "Button,createElement");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_31_3() {
+    // umd factory receiving exports
+    let source = r#"
+    !function(e,t){"object"==typeof exports&&"undefined"!=typeof module?t(exports,require("dep")):"function"==typeof define&&define.amd?define(["exports","dep"],t):t((e="undefined"!=typeof globalThis?globalThis:e||self).myLib={},e.dep)}(this,(function(e,t){"use strict";e.foo=function(){},e.bar=t.bar,Object.defineProperty(e,"__esModule",{value:!0})}));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar,__esModule");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_32() {
+    // webpack 5 `libraryTarget: 'commonjs2'` output
+    let source = r#"
+    /******/ (() => { // webpackBootstrap
+    /******/ 	"use strict";
+    /******/ 	var __webpack_modules__ = ({
+    /***/ "./src/dep.js":
+    /***/ ((module) => {
+    module.exports = function dep() {};
+    /***/ })
+    /******/ 	});
+    /******/ 	var __webpack_module_cache__ = {};
+    /******/ 	function __webpack_require__(moduleId) {
+    /******/ 		var cachedModule = __webpack_module_cache__[moduleId];
+    /******/ 		if (cachedModule !== undefined) {
+    /******/ 			return cachedModule.exports;
+    /******/ 		}
+    /******/ 		var module = __webpack_module_cache__[moduleId] = {
+    /******/ 			exports: {}
+    /******/ 		};
+    /******/ 		__webpack_modules__[moduleId](module, module.exports, __webpack_require__);
+    /******/ 		return module.exports;
+    /******/ 	}
+    /******/ 	(() => {
+    /******/ 		__webpack_require__.n = (module) => {
+    /******/ 			var getter = module && module.__esModule ?
+    /******/ 				() => (module['default']) :
+    /******/ 				() => (module);
+    /******/ 			__webpack_require__.d(getter, { a: getter });
+    /******/ 			return getter;
+    /******/ 		};
+    /******/ 	})();
+    /******/ 	(() => {
+    /******/ 		__webpack_require__.d = (exports, definition) => {
+    /******/ 			for(var key in definition) {
+    /******/ 				if(__webpack_require__.o(definition, key) && !__webpack_require__.o(exports, key)) {
+    /******/ 					Object.defineProperty(exports, key, { enumerable: true, get: definition[key] });
+    /******/ 				}
+    /******/ 			}
+    /******/ 		};
+    /******/ 	})();
+    /******/ 	(() => {
+    /******/ 		__webpack_require__.o = (obj, prop) => (Object.prototype.hasOwnProperty.call(obj, prop))
+    /******/ 	})();
+    /******/ 	(() => {
+    /******/ 		__webpack_require__.r = (exports) => {
+    /******/ 			if(typeof Symbol !== 'undefined' && Symbol.toStringTag) {
+    /******/ 				Object.defineProperty(exports, Symbol.toStringTag, { value: 'Module' });
+    /******/ 			}
+    /******/ 			Object.defineProperty(exports, '__esModule', { value: true });
+    /******/ 		};
+    /******/ 	})();
+    var __webpack_exports__ = {};
+    (() => {
+    __webpack_require__.r(__webpack_exports__);
+    /* harmony export */ __webpack_require__.d(__webpack_exports__, {
+    /* harmony export */   "default": () => (__WEBPACK_DEFAULT_EXPORT__),
+    /* harmony export */   "my-name": () => (/* binding */ myName),
+    /* harmony export */   dep: () => (/* reexport default from dynamic */ _dep_js__WEBPACK_IMPORTED_MODULE_0___default.a)
+    /* harmony export */ });
+    /* harmony import */ var _dep_js__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("./src/dep.js");
+    /* harmony import */ var _dep_js__WEBPACK_IMPORTED_MODULE_0___default = /*#__PURE__*/__webpack_require__.n(_dep_js__WEBPACK_IMPORTED_MODULE_0__);
+    const myName = 1;
+    /* harmony default export */ const __WEBPACK_DEFAULT_EXPORT__ = (2);
+    })();
+    module.exports = __webpack_exports__;
+    /******/ })()
+    ;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,default,my-name,dep");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_32_1() {
+    // webpack 5 `libraryTarget: 'commonjs'` output with a library name, minified
+    let source = r#"
+    (()=>{"use strict";var e={d:(o,r)=>{for(var t in r)e.o(r,t)&&!e.o(o,t)&&Object.defineProperty(o,t,{enumerable:!0,get:r[t]})},o:(e,o)=>Object.prototype.hasOwnProperty.call(e,o),r:e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})}},o={};e.r(o),e.d(o,{default:()=>t,foo:()=>r});const r=1,t=2;exports.MyLib=o})();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "MyLib");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_32_2() {
+    // webpack 5 `libraryTarget: 'commonjs2'` output, minified
+    let source = r#"
+    (()=>{"use strict";var e={d:(o,r)=>{for(var t in r)e.o(r,t)&&!e.o(o,t)&&Object.defineProperty(o,t,{enumerable:!0,get:r[t]})},o:(e,o)=>Object.prototype.hasOwnProperty.call(e,o),r:e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})}},o={};e.r(o),e.d(o,{"a-b":()=>n,default:()=>t,foo:()=>r});const r=1,t=2,n=3;module.exports=o})();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,a-b,default,foo");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_32_3() {
+    // webpack 5 minified umd output with string keys and a long runtime
+    let source = r#"
+    !function(e,t){"object"==typeof exports&&"object"==typeof module?module.exports=t():"function"==typeof define&&define.amd?define([],t):"object"==typeof exports?exports.lib=t():e.lib=t()}(this,(()=>(()=>{"use strict";var e={n:t=>{var r=t&&t.__esModule?()=>t.default:()=>t;return e.d(r,{a:r}),r},d:(t,r)=>{for(var o in r)e.o(r,o)&&!e.o(t,o)&&Object.defineProperty(t,o,{enumerable:!0,get:r[o]})},o:(e,t)=>Object.prototype.hasOwnProperty.call(e,t),r:e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})}},t={};function r(){}function o(){}function n(){}function a(){}function i(){}function c(){}function u(){}function s(){}function l(){}return e.r(t),e.d(t,{"default":()=>r,"my-name":()=>o}),t})()));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,default,my-name");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_32_4() {
+    // webpack 5 minified umd output with an internal namespace object
+    let source = r#"
+    !function(e,t){"object"==typeof exports&&"object"==typeof module?module.exports=t():"function"==typeof define&&define.amd?define([],t):"object"==typeof exports?exports.lib=t():e.lib=t()}(this,(()=>(()=>{"use strict";var e={d:(t,r)=>{for(var o in r)e.o(r,o)&&!e.o(t,o)&&Object.defineProperty(t,o,{enumerable:!0,get:r[o]})},o:(e,t)=>Object.prototype.hasOwnProperty.call(e,t),r:e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})}},t={};var ns={};e.r(ns),e.d(ns,{internal:()=>x});const x=1;function r(){}return e.r(t),e.d(t,{"default":()=>r,ns:()=>ns}),t})()));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,default,ns");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33() {
+    // parcel output
+    let source = r#"
+    function $parcel$export(e, n, v, s) {
+      Object.defineProperty(e, n, {get: v, set: s, enumerable: true, configurable: true});
+    }
+    function $parcel$exportWildcard(dest, source) {
+      Object.keys(source).forEach(function(key) {
+        if (key === 'default' || key === '__esModule' || Object.prototype.hasOwnProperty.call(dest, key)) {
+          return;
+        }
+        Object.defineProperty(dest, key, {
+          enumerable: true,
+          get: function get() {
+            return source[key];
+          }
+        });
+      });
+      return dest;
+    }
+    function $parcel$defineInteropFlag(a) {
+      Object.defineProperty(a, '__esModule', {value: true, configurable: true});
+    }
+    $parcel$defineInteropFlag(module.exports);
+    $parcel$export(module.exports, "foo", () => $4fa36e821943b400$export$6a5cdcad870c7d9c);
+    $parcel$export(module.exports, "default", () => $4fa36e821943b400$export$2e2bcd8739ae039);
+    var $8d0a1c2ec2ff9b3d$exports = require("./bar");
+    $parcel$exportWildcard(module.exports, $8d0a1c2ec2ff9b3d$exports);
+    const $4fa36e821943b400$export$6a5cdcad870c7d9c = 1;
+    var $4fa36e821943b400$export$2e2bcd8739ae039 = 2;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,foo,default");
+    assert_eq!(reexports.join(","), "./bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33_1() {
+    // minified parcel output
+    let source = r#"
+    function e(e,t,r,n){Object.defineProperty(e,t,{get:r,set:n,enumerable:!0,configurable:!0})}function t(e){Object.defineProperty(e,"__esModule",{value:!0,configurable:!0})}t(module.exports),e(module.exports,"foo",()=>r),e(module.exports,"my-name",()=>n);const r=1,n=2;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,foo,my-name");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33_2() {
+    // swc output
+    let source = r#"
+    "use strict";
+    Object.defineProperty(exports, "__esModule", {
+        value: true
+    });
+    function _export(target, all) {
+        for(var name in all)Object.defineProperty(target, name, {
+            enumerable: true,
+            get: all[name]
+        });
+    }
+    _export(exports, {
+        default: function() {
+            return _default;
+        },
+        foo: function() {
+            return foo;
+        }
+    });
+    _export_star(require("./bar"), exports);
+    function _export_star(from, to) {
+        Object.keys(from).forEach(function(k) {
+            if (k !== "default" && !Object.prototype.hasOwnProperty.call(to, k)) {
+                Object.defineProperty(to, k, {
+                    enumerable: true,
+                    get: function() {
+                        return from[k];
+                    }
+                });
+            }
+        });
+        return from;
+    }
+    const foo = 1;
+    const _default = 2;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,default,foo");
+    assert_eq!(reexports.join(","), "./bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33_3() {
+    // minified swc output
+    let source = r#"
+    "use strict";Object.defineProperty(exports,"__esModule",{value:!0});function e(e,t){for(var r in t)Object.defineProperty(e,r,{enumerable:!0,get:t[r]})}e(exports,{bar:function(){return n},foo:function(){return o}}),t(require("./baz"),exports);function t(e,t){return Object.keys(e).forEach(function(r){"default"===r||Object.prototype.hasOwnProperty.call(t,r)||Object.defineProperty(t,r,{enumerable:!0,get:function(){return e[r]}})}),e}const o=1,n=2;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,bar,foo");
+    assert_eq!(reexports.join(","), "./baz");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33_4() {
+    // swc output using @swc/helpers
+    let source = r#"
+    "use strict";
+    Object.defineProperty(exports, "__esModule", {
+        value: true
+    });
+    const _export_star = require("@swc/helpers/_/_export_star");
+    _export_star._(require("./bar"), exports);
+    (0, _export_star._)(require("./baz"), exports);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule");
+    assert_eq!(reexports.join(","), "./bar,./baz");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_33_5() {
+    // rollup output
+    let source = r#"
+    'use strict';
+
+    Object.defineProperty(exports, '__esModule', { value: true });
+
+    var dep = require('dep');
+
+    const foo = 1;
+
+    exports.foo = foo;
+    Object.keys(dep).forEach(function (k) {
+      if (k !== 'default' && !Object.prototype.hasOwnProperty.call(exports, k)) Object.defineProperty(exports, k, {
+        enumerable: true,
+        get: function () { return dep[k]; }
+      });
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,foo");
+    assert_eq!(reexports.join(","), "dep");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_34() {
+    // minified tslib helpers
+    let source = r#"
+    "use strict";
+    var n = this && this.__createBinding || (Object.create ? function (e, t, r, n) { void 0 === n && (n = r); var o = Object.getOwnPropertyDescriptor(t, r); o && !("get" in o ? !t.__esModule : o.writable || o.configurable) || (o = { enumerable: !0, get: function () { return t[r] } }), Object.defineProperty(e, n, o) } : function (e, t, r, n) { void 0 === n && (n = r), e[n] = t[r] }),
+      o = this && this.__exportStar || function (e, t) { for (var r in e) "default" === r || Object.prototype.hasOwnProperty.call(t, r) || n(t, e, r) };
+    Object.defineProperty(exports, "__esModule", { value: !0 }), o(require("./a"), exports), o(require("./b"), exports);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule");
+    assert_eq!(reexports.join(","), "./a,./b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_34_1() {
+    // minified typescript `__export` helper
+    let source = r#"
+    "use strict";function r(e){for(var t in e)exports.hasOwnProperty(t)||(exports[t]=e[t])}Object.defineProperty(exports,"__esModule",{value:!0}),r(require("./a")),r({foo:1,bar:2});
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,foo,bar");
+    assert_eq!(reexports.join(","), "./a");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_34_2() {
+    // helpers calling other helpers
+    let source = r#"
+    var __defProp = Object.defineProperty;
+    var __getOwnPropNames = Object.getOwnPropertyNames;
+    var __hasOwnProp = Object.prototype.hasOwnProperty;
+    var __copyProps = (to, from, except, desc) => {
+      if (from && typeof from === "object" || typeof from === "function") {
+        for (let key of __getOwnPropNames(from))
+          if (!__hasOwnProp.call(to, key) && key !== except)
+            __defProp(to, key, { get: () => from[key], enumerable: true });
+      }
+      return to;
+    };
+    var a = (target, mod, secondTarget) => (__copyProps(target, mod, "default"), secondTarget && __copyProps(secondTarget, mod, "default"));
+    var b = (target, all) => {
+      for (var name in all)
+        __defProp(target, name, { get: all[name], enumerable: true });
+    };
+    var c = (e, t) => __defProp(e, t, { get: () => 1, enumerable: true });
+    b(module.exports, { foo: () => foo, bar: () => bar });
+    c(exports, "baz");
+    a(module.exports, require("./lib"));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar,baz");
+    assert_eq!(reexports.join(","), "./lib");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_34_3() {
+    // functions that do more than defining exports are not helpers
+    let source = r#"
+    function setup(e, t) {
+      Object.defineProperty(e, "__esModule", { value: true });
+      e.foo = t;
+    }
+    function copy(e, t) {
+      for (var k in t) e[k] = t[k];
+      e.bar = 1;
+    }
+    setup(exports, 1);
+    copy(exports, require("./lib"));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "__esModule,foo,bar");
+    assert_eq!(reexports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_34_4() {
+    // loops calling functions that are not binding helpers
+    let source = r#"
+    function forOwn(target, source) { for (var k in source) log(target, source, k) }
+    function validate(schema, input) { for (var key in input) check(schema, input, key) }
+    forOwn(exports, require('./x'));
+    validate(module.exports, require('./config'));
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "");
+
+    // typescript binding helpers
+    let source = r#"
+    var __createBinding = (this && this.__createBinding) || (Object.create ? (function(o, m, k, k2) {
+      if (k2 === undefined) k2 = k;
+      var desc = Object.getOwnPropertyDescriptor(m, k);
+      if (!desc || ("get" in desc ? !m.__esModule : desc.writable || desc.configurable)) {
+        desc = { enumerable: true, get: function() { return m[k]; } };
+      }
+      Object.defineProperty(o, k2, desc);
+    }) : (function(o, m, k, k2) {
+      if (k2 === undefined) k2 = k;
+      o[k2] = m[k];
+    }));
+    var __exportStar = (this && this.__exportStar) || function(m, exports) {
+      for (var p in m) if (p !== "default" && !Object.prototype.hasOwnProperty.call(exports, p)) __createBinding(exports, m, p);
+    };
+    __exportStar(require('./y'), exports);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (_, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(reexports.join(","), "./y");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_35() {
+    // emscripten output with `-sMODULARIZE`
+    let source = r#"
+    var Module = (() => {
+      var _scriptDir = typeof document !== 'undefined' && document.currentScript ? document.currentScript.src : undefined;
+      if (typeof __filename !== 'undefined') _scriptDir = _scriptDir || __filename;
+      return (
+    function(moduleArg = {}) {
+
+    var Module = moduleArg;
+    var readyPromiseResolve, readyPromiseReject;
+    Module['ready'] = new Promise((resolve, reject) => {
+      readyPromiseResolve = resolve;
+      readyPromiseReject = reject;
+    });
+    var moduleOverrides = Object.assign({}, Module);
+    var ENVIRONMENT_IS_WEB = typeof window == 'object';
+    var ENVIRONMENT_IS_NODE = typeof process == 'object' && typeof process.versions == 'object' && typeof process.versions.node == 'string';
+    if (ENVIRONMENT_IS_NODE) {
+      var fs = require('fs');
+      var nodePath = require('path');
+      if (typeof module != 'undefined') {
+        module['exports'] = Module;
+      }
+    }
+    function receiveInstance(instance, module) {
+      var exports = instance.exports;
+      Module['asm'] = exports;
+      return exports;
+    }
+    var _add = Module['_add'] = (a0, a1) => (_add = Module['_add'] = Module['asm']['add'])(a0, a1);
+    Module['ccall'] = ccall;
+    return moduleArg.ready
+    }
+    );
+    })();
+    if (typeof exports === 'object' && typeof module === 'object')
+      module.exports = Module;
+    else if (typeof define === 'function' && define['amd'])
+      define([], () => Module);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", true)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_35_1() {
+    // emscripten output with `-sMODULARIZE -sEXPORT_NAME=createModule`, minified
+    let source = r#"
+    var createModule=(()=>{var _scriptDir=typeof document!="undefined"&&document.currentScript?document.currentScript.src:undefined;if(typeof __filename!="undefined")_scriptDir=_scriptDir||__filename;return function(moduleArg={}){var Module=moduleArg;var readyPromiseResolve,readyPromiseReject;Module["ready"]=new Promise((resolve,reject)=>{readyPromiseResolve=resolve;readyPromiseReject=reject});Module["ccall"]=ccall;return moduleArg.ready}})();if(typeof exports==="object"&&typeof module==="object")module.exports=createModule;else if(typeof define==="function"&&define["amd"])define([],()=>createModule);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_35_2() {
+    // wasm-bindgen `--target nodejs` output, as in this crate's own `pkg/esm_cjs_lexer.js`
+    let source = r#"
+    let imports = {};
+    imports['__wbindgen_placeholder__'] = module.exports;
+    let wasm;
+    const { TextDecoder, TextEncoder } = require(`util`);
+
+    let cachedTextDecoder = new TextDecoder('utf-8', { ignoreBOM: true, fatal: true });
+
+    cachedTextDecoder.decode();
+
+    function getStringFromWasm0(ptr, len) {
+        ptr = ptr >>> 0;
+        return cachedTextDecoder.decode(getUint8Memory0().subarray(ptr, ptr + len));
+    }
+    /**
+    * @param {string} specifier
+    * @param {string} code
+    * @param {any} options
+    * @returns {any}
+    */
+    module.exports.parse = function(specifier, code, options) {
+        try {
+            const retptr = wasm.__wbindgen_add_to_stack_pointer(-16);
+            wasm.parse(retptr, ptr0, len0, ptr1, len1, addHeapObject(options));
+            return takeObject(r0);
+        } finally {
+            wasm.__wbindgen_add_to_stack_pointer(16);
+        }
+    };
+
+    module.exports.__wbindgen_error_new = function(arg0, arg1) {
+        const ret = new Error(getStringFromWasm0(arg0, arg1));
+        return addHeapObject(ret);
+    };
+
+    module.exports.__wbindgen_object_drop_ref = function(arg0) {
+        takeObject(arg0);
+    };
+
+    class Lexer {
+        free() {}
+    }
+    module.exports.Lexer = Lexer;
+
+    const path = require('path').join(__dirname, 'esm_cjs_lexer_bg.wasm');
+    const bytes = require('fs').readFileSync(path);
+
+    const wasmModule = new WebAssembly.Module(bytes);
+    const wasmInstance = new WebAssembly.Instance(wasmModule, imports);
+    wasm = wasmInstance.exports;
+    module.exports.__wasm = wasm;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(
+      exports.join(","),
+      "parse,__wbindgen_error_new,__wbindgen_object_drop_ref,Lexer,__wasm"
+    );
+    assert_eq!(reexports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_35_3() {
+    // emscripten output without `-sMODULARIZE`
+    let source = r#"
+    var Module = typeof Module != 'undefined' ? Module : {};
+    exports.ready = true;
+    var ENVIRONMENT_IS_NODE = typeof process == 'object' && typeof process.versions == 'object' && typeof process.versions.node == 'string';
+    if (ENVIRONMENT_IS_NODE) {
+      if (typeof module != 'undefined') {
+        module['exports'] = Module;
+      }
+    }
+    Module['ccall'] = ccall;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_36() {
+    let source = r#"
+    define(['exports', 'dep', 'require'], function (exports, dep, require) {
+      'use strict';
+      exports.a = 1;
+      exports.b = dep.b;
+      Object.defineProperty(exports, '__esModule', { value: true });
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a,b,__esModule");
+    assert!(analysis.reexports.is_empty());
+    assert_eq!(analysis.requires.join(","), "dep");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_36_1() {
+    let source = r#"
+    define(function () {
+      function foo() {}
+      return { foo: foo, bar: 'bar' };
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,bar");
+    assert_eq!(analysis.requires.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_36_2() {
+    let source = r#"
+    define('my-lib', ['module', './a', './b'], function (m, a, b) {
+      m.exports = a;
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "");
+    assert_eq!(analysis.reexports, vec![Reexport::namespace("./a".to_owned())]);
+    assert_eq!(analysis.requires.join(","), "./a,./b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_36_3() {
+    // simplified commonjs wrapper
+    let source = r#"
+    define(function (require, exports, module) {
+      var dep = require('./dep');
+      exports.foo = dep.foo;
+      module.exports.bar = 'bar';
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_36_4() {
+    let source = r#"
+    define(['./a'], function (a) {
+      return a;
+    });
+    define({ nope: true });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "nope");
+    assert!(analysis.reexports.is_empty());
+    assert_eq!(analysis.requires.join(","), "./a");
+
+    let source = r#"
+    define(['./a'], function (a) {
+      return a;
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "");
+    assert_eq!(analysis.reexports, vec![Reexport::namespace("./a".to_owned())]);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_37() {
+    let source = r#"
+    module.exports = require('./lib').default;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(analysis.exports.is_empty());
+    assert_eq!(
+      analysis.reexports,
+      vec![Reexport {
+        specifier: "./lib".to_owned(),
+        kind: ReexportKind::Member,
+        member: Some("default".to_owned()),
+        args: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_37_1() {
+    let source = r#"
+    const opts = { debug: true };
+    module.exports = require('./lib')('app', 2, false, opts);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(analysis.exports.is_empty());
+    assert_eq!(
+      analysis.reexports,
+      vec![Reexport {
+        specifier: "./lib".to_owned(),
+        kind: ReexportKind::Call,
+        member: None,
+        args: Some(vec![
+          ReexportArg::String("app".to_owned()),
+          ReexportArg::Number(ArgNumber::new(2.0).unwrap()),
+          ReexportArg::Bool(false),
+          ReexportArg::Null,
+        ]),
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_37_2() {
+    let source = r#"
+    const lib = require('./lib');
+    module.exports = lib['default'];
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "./lib.default");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_37_3() {
+    // `-0` equals `0`, `NaN` and `Infinity` are not representable
+    let source = r#"
+    module.exports = require('./lib')(-0, NaN, Infinity, 1e3);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(
+      analysis.reexports[0].args,
+      Some(vec![
+        ReexportArg::Number(ArgNumber::new(0.0).unwrap()),
+        ReexportArg::Null,
+        ReexportArg::Null,
+        ReexportArg::Number(ArgNumber::new(1000.0).unwrap()),
+      ])
+    );
+    assert_eq!(ArgNumber::new(-0.0), ArgNumber::new(0.0));
+    assert_eq!(ArgNumber::new(f64::NAN), None);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_38() {
+    let source = r#"
+    const x = require('./x');
+    exports.foo = require('./x').bar;
+    exports.x = x;
+    exports.y = x.y;
+    exports.z = 'z';
+    module.exports.w = require('./w')['default'];
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,x,y,z,w");
+    let sources: Vec<(&str, &str, &str)> = analysis
+      .sources
+      .iter()
+      .map(|(name, source)| (name.as_str(), source.specifier.as_str(), source.imported.as_str()))
+      .collect();
+    assert_eq!(
+      sources,
+      vec![
+        ("foo", "./x", "bar"),
+        ("x", "./x", "*"),
+        ("y", "./x", "y"),
+        ("w", "./w", "default"),
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_38_1() {
+    let source = r#"
+    const b = require('./b');
+    module.exports = { a: require('./a'), b: b.b, c: 'c', b };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a,b,c");
+    assert_eq!(analysis.sources.len(), 2);
+    assert_eq!(
+      analysis.sources.get("a"),
+      Some(&ExportSource {
+        specifier: "./a".to_owned(),
+        imported: "*".to_owned(),
+      })
+    );
+    assert_eq!(
+      analysis.sources.get("b"),
+      Some(&ExportSource {
+        specifier: "./b".to_owned(),
+        imported: "*".to_owned(),
+      })
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_38_2() {
+    // a later assignment drops the binding
+    let source = r#"
+    exports.foo = require('./foo');
+    Object.defineProperty(exports, 'bar', { value: require('./bar').bar });
+    exports.foo = function () {};
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,bar");
+    assert_eq!(analysis.sources.keys().cloned().collect::<Vec<_>>().join(","), "bar");
+    assert_eq!(analysis.sources["bar"].imported, "bar");
+
+    // sources are dropped when `module.exports` is replaced
+    let source = r#"
+    exports.foo = require('./foo');
+    module.exports = { bar: 'bar' };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "bar");
+    assert!(analysis.sources.is_empty());
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_39() {
+    let source = r#"
+    const version = '1.0.0';
+    class Parser {}
+    function parse() {}
+    const options = { strict: true };
+    exports.version = version;
+    exports.Parser = Parser;
+    exports.parse = parse;
+    exports.options = options;
+    exports.count = 1 + 2;
+    exports.list = [];
+    exports.lazy = () => {};
+    exports.dep = require('./dep');
+    exports.a = exports.b = undefined;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    let kinds: Vec<(&str, ValueKind)> = analysis
+      .kinds
+      .iter()
+      .map(|(name, kind)| (name.as_str(), *kind))
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![
+        ("version", ValueKind::Primitive),
+        ("Parser", ValueKind::Class),
+        ("parse", ValueKind::Function),
+        ("options", ValueKind::Object),
+        ("count", ValueKind::Primitive),
+        ("list", ValueKind::Object),
+        ("lazy", ValueKind::Function),
+        ("dep", ValueKind::Unknown),
+        ("a", ValueKind::Primitive),
+        ("b", ValueKind::Primitive),
+      ]
+    );
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_39_1() {
+    let source = r#"
+    function debug() {}
+    debug.enable = function () {};
+    module.exports = debug;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "enable");
+    assert_eq!(analysis.kinds["enable"], ValueKind::Function);
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+
+    let source = r#"
+    module.exports = class Client {
+      static create() {}
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports_kind, ValueKind::Class);
+    assert_eq!(analysis.exports.join(","), "create");
+    assert_eq!(analysis.kinds["create"], ValueKind::Function);
+
+    let source = r#"
+    module.exports = 'module';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports_kind, ValueKind::Primitive);
+
+    let source = r#"
+    module.exports = require('./lib');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports_kind, ValueKind::Unknown);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_39_2() {
+    let source = r#"
+    function create() {
+      return { foo: 'bar', run() {} };
+    }
+    module.exports = create();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,run");
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+    assert_eq!(analysis.kinds["foo"], ValueKind::Primitive);
+    assert_eq!(analysis.kinds["run"], ValueKind::Function);
+
+    let source = r#"
+    module.exports = function () {
+      return { foo: 'bar' };
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", true).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo");
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_39_3() {
+    let source = r#"
+    var api = {};
+    api.get = function () {};
+    api.VERSION = '1.0.0';
+    module.exports = api;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "get,VERSION");
+    assert_eq!(analysis.kinds["get"], ValueKind::Function);
+    assert_eq!(analysis.kinds["VERSION"], ValueKind::Primitive);
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_39_4() {
+    // webpack 5 `libraryTarget: 'commonjs2'` output, minified
+    let source = r#"
+    (()=>{"use strict";var e={d:(o,r)=>{for(var t in r)e.o(r,t)&&!e.o(o,t)&&Object.defineProperty(o,t,{enumerable:!0,get:r[t]})},o:(e,o)=>Object.prototype.hasOwnProperty.call(e,o),r:e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})}},o={};e.r(o),e.d(o,{foo:()=>r,Bar:()=>B,obj:()=>x,n:()=>1,dyn:g});function r(){}class B{}const x={a:1};module.exports=o})();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "__esModule,foo,Bar,obj,n,dyn");
+    assert_eq!(analysis.kinds["__esModule"], ValueKind::Primitive);
+    assert_eq!(analysis.kinds["foo"], ValueKind::Function);
+    assert_eq!(analysis.kinds["Bar"], ValueKind::Class);
+    assert_eq!(analysis.kinds["obj"], ValueKind::Object);
+    assert_eq!(analysis.kinds["n"], ValueKind::Primitive);
+    assert_eq!(analysis.kinds["dyn"], ValueKind::Unknown);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_40() {
+    let source = r#"
+    "use strict";
+    Object.defineProperty(exports, "__esModule", { value: true });
+    exports.default = void 0;
+    class Foo {}
+    exports.default = Foo;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(analysis.es_module);
+    assert!(analysis.has_default);
+    assert_eq!(analysis.default_kind, Some(ValueKind::Class));
+    assert_eq!(analysis.interop, Interop::Babel);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_40_1() {
+    let source = r#"
+    exports.__esModule = true;
+    exports.foo = 'bar';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(analysis.es_module);
+    assert!(!analysis.has_default);
+    assert_eq!(analysis.default_kind, None);
+    assert_eq!(analysis.interop, Interop::Babel);
+
+    let source = r#"
+    module.exports = function debug() {};
+    module.exports.default = module.exports;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(!analysis.es_module);
+    assert!(analysis.has_default);
+    assert_eq!(analysis.default_kind, Some(ValueKind::Function));
+    assert_eq!(analysis.interop, Interop::Node);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_41() {
+    let source = r#"
+    exports.foo = 'foo';
+    exports.internal = 'internal';
+    module.exports.bar = require('./bar');
+    delete exports.internal;
+    delete module.exports['bar'];
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo");
+    assert!(analysis.sources.is_empty());
+    assert_eq!(analysis.kinds.len(), 1);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_41_1() {
+    let source = r#"
+    exports.foo = 'foo';
+    exports = { bar: 'bar' };
+    exports.baz = 'baz';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo");
+
+    let source = r#"
+    var e = exports;
+    e.foo = 'foo';
+    e = {};
+    e.bar = 'bar';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_41_2() {
+    // `exports` still points to the replaced object
+    let source = r#"
+    module.exports = { foo: 'foo' };
+    exports.bar = 'bar';
+    this.baz = 'baz';
+    module.exports.qux = 'qux';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,qux");
+
+    let source = r#"
+    exports.ignored = true;
+    module.exports = exports = { foo: 'foo' };
+    exports.bar = 'bar';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar");
+
+    let source = r#"
+    exports = module.exports = function () {};
+    exports.foo = 'foo';
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo");
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_42() {
+    let source = r#"
+    const extra = () => {};
+    module.exports = Object.assign(require('./base'), { extra });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "extra");
+    assert_eq!(reexports.join(","), "./base");
+
+    let source = r#"
+    const a = { foo: 1 };
+    const b = { bar: 2 };
+    module.exports = Object.assign({}, a, b, { baz: 3 });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,bar,baz");
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_42_1() {
+    let source = r#"
+    const a = 'a', b = 'b';
+    module.exports = Object.freeze({ a, b });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a,b");
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+
+    let source = r#"
+    const api = Object.seal({ get() {} });
+    module.exports = Object.preventExtensions(api);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "get");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_42_2() {
+    let source = r#"
+    const api = Object.create(null);
+    api.foo = 'foo';
+    api['bar'] = function () {};
+    module.exports = Object.freeze(api);
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,bar");
+    assert_eq!(analysis.kinds["bar"], ValueKind::Function);
+
+    let source = r#"
+    function parse() {}
+    module.exports = Object.assign(parse, { parse, version: '1.0.0' });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "parse,version");
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_43() {
+    let source = r#"
+    const defaults = { debug: false };
+    module.exports = function (opts) {
+      function run() {}
+      return { ...defaults, ...opts, run };
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![CallArg::Object(
+            [("verbose".to_owned(), CallArg::Bool(true))].into_iter().collect(),
+          )],
+          instantiate: false,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "debug,verbose,run");
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_43_1() {
+    let source = r#"
+    module.exports = function (mode) {
+      if (mode === 'full') {
+        return { a: 1, b: 2 };
+      }
+      return { a: 1 };
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![CallArg::String("full".to_owned())],
+          instantiate: false,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a,b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_43_2() {
+    let source = r#"
+    class Base {
+      ready = false;
+      close() {}
+    }
+    module.exports = class Foo extends Base {
+      name = 'foo';
+      constructor(options) {
+        super();
+        this.a = 1;
+        this.options = options;
+      }
+      run() {}
+      get size() { return 0 }
+      static create() {}
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![],
+          instantiate: true,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "ready,close,name,a,options,run,size");
+    assert_eq!(analysis.exports_kind, ValueKind::Class);
+    assert_eq!(analysis.kinds["run"], ValueKind::Function);
+    assert_eq!(analysis.kinds["a"], ValueKind::Primitive);
+
+    let source = r#"
+    module.exports = function Counter(start) {
+      this.count = start;
+      this.increment = function () {};
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![CallArg::Number(1.0)],
+          instantiate: true,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "count,increment");
+    assert_eq!(analysis.kinds["count"], ValueKind::Primitive);
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_43_3() {
+    let source = r#"
+    module.exports = function Foo(mode) {
+      this.a = 1;
+      if (mode === 'none') {
+        return undefined;
+      }
+      return 1;
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    for mode in ["none", "one"] {
+      let analysis = swc
+        .analyze_cjs_with_call_mode(
+          "production",
+          Some(CallMode {
+            args: vec![CallArg::String(mode.to_owned())],
+            instantiate: true,
+          }),
+        )
+        .expect("could not parse exports");
+      assert_eq!(analysis.exports.join(","), "a");
+      assert_eq!(analysis.exports_kind, ValueKind::Function);
+    }
+
+    let source = r#"
+    class Foo {
+      constructor() {
+        this.a = 1;
+        return 1;
+      }
+    }
+    module.exports = new Foo();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a");
+
+    let source = r#"
+    module.exports = function Foo() {
+      this.a = 1;
+      return { b: 2 };
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![],
+          instantiate: true,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_44() {
+    let source = r#"
+    class Client {
+      static connect() {}
+      request() {}
+    }
+    Client.create = function () {};
+    Client.VERSION = '1.0.0';
+    Object.assign(Client, { defaults: {} });
+    Client.prototype.close = function () {};
+    module.exports = Client;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "connect,create,VERSION,defaults");
+    assert_eq!(analysis.kinds["create"], ValueKind::Function);
+    assert_eq!(analysis.kinds["VERSION"], ValueKind::Primitive);
+    assert_eq!(analysis.kinds["defaults"], ValueKind::Object);
+    assert_eq!(analysis.prototype.join(","), "request,close");
+    assert_eq!(analysis.exports_kind, ValueKind::Class);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_44_1() {
+    let source = r#"
+    function Emitter(name) {
+      this.name = name;
+    }
+    Emitter.prototype.on = function () {};
+    Object.assign(Emitter.prototype, { off() {}, emit() {} });
+    Emitter.init = function () {};
+    module.exports = Emitter;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "init");
+    assert_eq!(analysis.prototype.join(","), "on,off,emit");
+
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![],
+          instantiate: true,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "name,on,off,emit");
+    assert_eq!(analysis.kinds["emit"], ValueKind::Function);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_44_2() {
+    let source = r#"
+    function Stream() {}
+    Stream.prototype.pipe = function () {};
+    Stream.prototype = { read: function () {}, write() {} };
+    module.exports = Stream;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "");
+    assert_eq!(analysis.prototype.join(","), "read,write");
+
+    let source = r#"
+    class Base {
+      run() {}
+    }
+    class Task extends Base {
+      constructor() {
+        super();
+        this.id = 1;
+      }
+      stop() {}
+    }
+    Task.prototype.pause = function () {};
+    module.exports = Task;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![],
+          instantiate: true,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "run,id,stop,pause");
+    assert_eq!(analysis.prototype.join(","), "stop,pause");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_45() {
+    let source = r#"
+    class Registry {
+      size = 0;
+      constructor(name) {
+        this.name = name;
+        this.items = {};
+      }
+      register() {}
+      static create() {}
+    }
+    module.exports = new Registry('default');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "size,name,items,register");
+    assert_eq!(analysis.kinds["name"], ValueKind::Primitive);
+    assert_eq!(analysis.kinds["register"], ValueKind::Function);
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+    assert!(!analysis.opaque_instance);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_45_1() {
+    let source = r#"
+    function Store() {
+      this.state = {};
+    }
+    Store.prototype.get = function () {};
+    module.exports = new Store;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "state,get");
+    assert!(!analysis.opaque_instance);
+
+    let source = r#"
+    const EventEmitter = require('events');
+    exports.foo = 'foo';
+    module.exports = new EventEmitter();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "");
+    assert!(analysis.reexports.is_empty());
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+    assert!(analysis.opaque_instance);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_45_2() {
+    // instances of local classes extending unknown classes
+    let source = r#"
+    const EventEmitter = require('events');
+    class Registry extends EventEmitter {
+      constructor() {
+        super();
+        this.items = {};
+      }
+      add() {}
+    }
+    module.exports = new Registry();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "items,add");
+    assert!(analysis.opaque_instance);
+
+    // local base classes are known
+    let source = r#"
+    class Base {
+      run() {}
+    }
+    function Store() {
+      this.state = {};
+    }
+    Store.prototype.get = function () {};
+    class Registry extends Base {
+      add() {}
+    }
+    class Cache extends Store {
+      clear() {}
+    }
+    module.exports = new Registry();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "run,add");
+    assert!(!analysis.opaque_instance);
+
+    let swc =
+      SWC::parse("index.cjs", &source.replace("new Registry()", "new Cache()")).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "state,get,clear");
+    assert!(!analysis.opaque_instance);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_45_3() {
+    // the `instanceof` guard calls the constructor again
+    let source = r#"
+    function Foo(o) {
+      if (!(this instanceof Foo)) return new Foo(o);
+      this.a = 1;
+    }
+    Foo.prototype.run = function () {};
+    module.exports = new Foo();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a,run");
+
+    let source = r#"
+    function Foo(o) {
+      if (!(this instanceof Foo)) return new Foo(o);
+      this.a = 1;
+    }
+    module.exports = Foo;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc
+      .analyze_cjs_with_call_mode(
+        "production",
+        Some(CallMode {
+          args: vec![],
+          instantiate: false,
+        }),
+      )
+      .expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "a");
+    assert_eq!(analysis.exports_kind, ValueKind::Function);
+
+    let source = r#"
+    function Foo() {
+      return new Foo();
+    }
+    module.exports = new Foo();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert!(analysis.exports.is_empty());
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_46() {
+    let source = r#"
+    Object.defineProperty(module, 'exports', {
+      enumerable: true,
+      get() {
+        return require('./impl');
+      }
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "");
+    assert_eq!(reexports.join(","), "./impl");
+
+    let source = r#"
+    Object.defineProperty(module, 'exports', {
+      get: () => ({ foo: 'bar', run() {} })
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo,run");
+    assert_eq!(analysis.exports_kind, ValueKind::Object);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_46_1() {
+    // typescript reexports
+    let source = r#"
+    "use strict";
+    Object.defineProperty(exports, "__esModule", { value: true });
+    exports.parse = exports.Parser = void 0;
+    var parser_1 = require("./parser");
+    Object.defineProperty(exports, "Parser", { enumerable: true, get: function () { return parser_1.Parser; } });
+    Object.defineProperty(exports, "parse", { enumerable: true, get: function () { return parser_1.default; } });
+    Object.defineProperty(exports, "utils", { enumerable: true, get() { return require("./utils"); } });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "__esModule,parse,Parser,utils");
+    let sources: Vec<(&str, &str, &str)> = analysis
+      .sources
+      .iter()
+      .map(|(name, source)| (name.as_str(), source.specifier.as_str(), source.imported.as_str()))
+      .collect();
+    assert_eq!(
+      sources,
+      vec![
+        ("parse", "./parser", "default"),
+        ("Parser", "./parser", "Parser"),
+        ("utils", "./utils", "*"),
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_47() {
+    let source = r#"
+    ['get', 'post', 'put'].forEach(function (method) {
+      exports[method] = function () {};
+    });
+    const verbs = ['head', 'options'];
+    verbs.forEach((verb, i) => {
+      module.exports[verb] = i;
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "get,post,put,head,options");
+    assert_eq!(analysis.kinds["get"], ValueKind::Function);
+    assert_eq!(analysis.kinds["head"], ValueKind::Primitive);
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_47_1() {
+    let source = r#"
+    for (const k of ['a', 'b']) module.exports[k] = k;
+    const names = ['c', 'd'];
+    for (let name of names) {
+      if (name !== 'd') {
+        exports[name] = true;
+      }
+    }
+    for (const x of unknown) exports[x] = x;
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b,c");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_47_2() {
+    let source = r#"
+    var utils = { isString: function () {}, VERSION: '1.0.0' };
+    for (var k in utils) exports[k] = utils[k];
+    Object.keys({ foo: 1 }).forEach(function (key) {
+      exports[key + 'Sync'] = function () {};
+    });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "isString,VERSION");
+    assert_eq!(analysis.kinds["isString"], ValueKind::Function);
+    assert_eq!(analysis.kinds["VERSION"], ValueKind::Primitive);
+
+    // webpack library target
+    let source = r#"
+    var __webpack_exports__ = {};
+    __webpack_require__.d(__webpack_exports__, { foo: () => foo, bar: () => bar });
+    var __webpack_export_target__ = exports;
+    for (var i in __webpack_exports__) __webpack_export_target__[i] = __webpack_exports__[i];
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "foo,bar");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_47_3() {
+    // long arrays are not unrolled
+    let names = (0..100).map(|i| format!("'m{}'", i)).collect::<Vec<_>>().join(",");
+    let source = format!(
+      r#"
+    [{names}].forEach(function (name) {{ exports[name] = 1 }});
+    for (const name of [{names}]) exports[name] = 1;
+    ['a', 'b'].forEach(function (name) {{ exports[name] = 1 }});
+    "#
+    );
+    let swc = SWC::parse("index.cjs", &source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(exports.join(","), "a,b");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_47_4() {
+    // nested loops share one unroll budget
+    let names = (0..60).map(|i| format!("'m{}'", i)).collect::<Vec<_>>().join(",");
+    let source = format!(
+      r#"
+    const names = [{names}];
+    for (const a of names) {{
+      for (const b of names) {{
+        for (const c of names) {{
+          for (const d of names) exports[d] = 1;
+        }}
+      }}
+    }}
+    ['x'].forEach(function (name) {{ exports[name] = 1 }});
+    "#
+    );
+    let swc = SWC::parse("index.cjs", &source).expect("could not parse module");
+    let (exports, _) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    // the budget runs out before the second unroll of the innermost loop, short loops still fit
+    assert_eq!(exports.len(), 61);
+    assert_eq!(exports.last().map(String::as_str), Some("x"));
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_48() {
+    let source = r#"
+    var r = require;
+    var req = module.require;
+    module.exports = {
+      ...r('./a'),
+      ...module.require('./b'),
+      ...require.call(null, './c'),
+      ...require.apply(null, ['./d']),
+      ...__non_webpack_require__('./e'),
+      ...req('./f'),
+    };
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (_, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(reexports.join(","), "./a,./b,./c,./d,./e,./f");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_48_1() {
+    // `require` is shadowed by a local variable or a parameter
+    let source = r#"
+    (function (require) {
+      module.exports = require('./a');
+    })(function () { return {} });
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (_, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(reexports.join(","), "");
+
+    let source = r#"
+    (function (require) {
+      exports.foo = require('./foo');
+    })();
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    assert_eq!(analysis.exports.join(","), "foo");
+    assert!(analysis.sources.is_empty());
+
+    let source = r#"
+    const require = (id) => ({ id });
+    module.exports = require('./b');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let (_, reexports) = swc
+      .parse_cjs_exports("production", false)
+      .expect("could not parse exports");
+    assert_eq!(reexports.join(","), "");
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_49() {
+    // classify the specifiers of reexports and sources
+    let source = r#"
+    module.exports = require('events');
+    module.exports.fs = require('node:fs');
+    module.exports.lib = require('./lib').lib;
+    module.exports.abs = require('/abs/path');
+    module.exports.pkg = require('lodash/fp');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    let specifiers = analysis.get_specifiers(None, false);
+    let kinds: Vec<(&str, SpecifierKind)> = specifiers
+      .iter()
+      .map(|(name, specifier)| (name.as_str(), specifier.kind))
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![
+        ("events", SpecifierKind::Builtin),
+        ("node:fs", SpecifierKind::Builtin),
+        ("./lib", SpecifierKind::Relative),
+        ("/abs/path", SpecifierKind::Absolute),
+        ("lodash/fp", SpecifierKind::Bare),
+      ]
+    );
+    assert!(specifiers.values().all(|specifier| specifier.exports.is_none()));
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_49_1() {
+    // builtins depend on the node version
+    let source = r#"
+    module.exports = require('node:events');
+    module.exports.promises = require('fs/promises');
+    module.exports.test = require('node:test');
+    module.exports.sqlite = require('sqlite');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    let kinds = |version: &str| -> Vec<SpecifierKind> {
+      analysis
+        .get_specifiers(parse_node_version(version).unwrap(), false)
+        .values()
+        .map(|specifier| specifier.kind)
+        .collect()
+    };
+    use SpecifierKind::{Bare, Builtin};
+    assert_eq!(kinds("v12.22.0"), vec![Bare, Bare, Bare, Bare]);
+    assert_eq!(kinds("14.17"), vec![Bare, Builtin, Bare, Bare]);
+    assert_eq!(kinds("14.18"), vec![Builtin, Builtin, Bare, Bare]);
+    assert_eq!(kinds("15"), vec![Bare, Builtin, Bare, Bare]);
+    assert_eq!(kinds("v18.17.0"), vec![Builtin, Builtin, Builtin, Bare]);
+    assert_eq!(kinds("latest"), vec![Builtin, Builtin, Builtin, Bare]);
+    assert!(parse_node_version("18.x").is_err());
+    assert!(parse_node_version("lts").is_err());
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_49_2() {
+    // known export names of builtins
+    let source = r#"
+    module.exports = require('node:path');
+    module.exports.cp = require('child_process');
+    module.exports.crypto = require('crypto');
+    module.exports.lib = require('./lib');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    let specifiers = analysis.get_specifiers(None, true);
+    let path = specifiers.get("node:path").unwrap().exports.as_ref().unwrap();
+    assert!(path.contains(&"join".to_owned()));
+    assert!(path.contains(&"resolve".to_owned()));
+    let child_process = specifiers.get("child_process").unwrap().exports.as_ref().unwrap();
+    assert!(child_process.contains(&"spawn".to_owned()));
+    assert!(!child_process.contains(&"_forkChild".to_owned()));
+    assert!(path.contains(&"matchesGlob".to_owned()));
+    assert_eq!(
+      specifiers.get("crypto"),
+      Some(&Specifier {
+        kind: SpecifierKind::Builtin,
+        exports: None,
+      })
+    );
+    assert_eq!(
+      specifiers.get("./lib"),
+      Some(&Specifier {
+        kind: SpecifierKind::Relative,
+        exports: None,
+      })
+    );
+  }
+
+  #[test]
+  fn parse_cjs_exports_case_49_3() {
+    // export names of builtins depend on the node version
+    let source = r#"
+    module.exports = require('node:events');
+    module.exports.fs = require('fs');
+    "#;
+    let swc = SWC::parse("index.cjs", source).expect("could not parse module");
+    let analysis = swc.analyze_cjs("production", false).expect("could not parse exports");
+    let exports = |version: &str, specifier: &str| -> Vec<String> {
+      let specifiers = analysis.get_specifiers(parse_node_version(version).unwrap(), true);
+      specifiers.get(specifier).unwrap().exports.clone().unwrap()
+    };
+    let events = exports("18.17.0", "node:events");
+    assert!(events.contains(&"once".to_owned()));
+    assert!(!events.contains(&"addAbortListener".to_owned()));
+    assert!(exports("20.5", "node:events").contains(&"addAbortListener".to_owned()));
+    let fs = exports("18.17.0", "fs");
+    assert!(fs.contains(&"cp".to_owned()));
+    assert!(!fs.contains(&"openAsBlob".to_owned()));
+    assert!(exports("latest", "fs").contains(&"openAsBlob".to_owned()));
+  }
 }